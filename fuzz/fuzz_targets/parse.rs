@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Core files are untrusted input, so parsing must never panic. Errors are
+// fine, and the parsed core should be safe to inspect.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(core) = licore::Core::parse(data) {
+        let _ = format!("{core:?}");
+        let _ = core.summary();
+        let _ = core.address_space_map();
+    }
+});
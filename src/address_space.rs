@@ -0,0 +1,114 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::core::{Core, SegmentFlags};
+
+/// A region of the virtual address space of the crashed process.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AddressRegion<'d> {
+    pub start: usize,
+    pub end: usize,
+    pub kind: RegionKind,
+    /// Path of the file mapped into this region, if any.
+    pub file_path: Option<&'d [u8]>,
+    /// Memory permissions of this region, if known.
+    pub flags: Option<SegmentFlags>,
+    /// Whether all bytes of this region are present in the core file.
+    pub present: bool,
+}
+
+impl fmt::Debug for AddressRegion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AddressRegion")
+            .field("start", &format_args!("{:#x}", self.start))
+            .field("end", &format_args!("{:#x}", self.end))
+            .field("kind", &self.kind)
+            .field("file_path", &self.file_path.map(String::from_utf8_lossy))
+            .field("flags", &self.flags)
+            .field("present", &self.present)
+            .finish()
+    }
+}
+
+/// Fixed address range of the legacy x86-64 vsyscall page.
+const VSYSCALL_RANGE: Range<usize> = 0xffff_ffff_ff60_0000..0xffff_ffff_ff60_1000;
+
+/// Path the kernel reports for the vsyscall page.
+const VSYSCALL_PATH: &[u8] = b"[vsyscall]";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RegionKind {
+    /// The region is covered by a segment or a file mapping.
+    Mapped,
+    /// The region lies between two mapped regions and is not mapped itself.
+    Gap,
+    /// The legacy vsyscall page at `0xffffffffff600000`.
+    ///
+    /// The kernel maps this page into every process. Depending on the kernel
+    /// configuration, its contents are missing from the core file or dumped
+    /// as zeros, so they cannot be used for unwinding. Backtraces of older
+    /// processes that still call into the vsyscall page can contain frames in
+    /// this region.
+    Vsyscall,
+}
+
+impl<'d> Core<'d> {
+    /// Return the address space of the crashed process, sorted by address.
+    ///
+    /// The map combines the loaded segments and the file mappings. Regions
+    /// are split wherever a segment or file mapping starts or ends, and
+    /// unmapped ranges between mapped regions are reported as
+    /// [`RegionKind::Gap`] regions.
+    pub fn address_space_map(&self) -> Vec<AddressRegion<'d>> {
+        let mut bounds: Vec<usize> = Vec::new();
+        for segment in &self.segments {
+            bounds.extend([segment.vm_start, segment.vm_end]);
+        }
+        for mapping in &self.file_map {
+            bounds.extend([mapping.vm_start, mapping.vm_end]);
+        }
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut mappings: Vec<_> = self.file_map.iter().collect();
+        mappings.sort_by_key(|m| m.vm_start);
+
+        let mut regions = Vec::new();
+        for pair in bounds.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            let segment = self.segment_at(start);
+            // File mappings don't overlap, so the one with the highest start
+            // address not above `start` is the only candidate.
+            let pos = mappings.partition_point(|m| m.vm_start <= start);
+            let mapping = pos
+                .checked_sub(1)
+                .map(|i| mappings[i])
+                .filter(|m| m.contains(start));
+
+            let is_vsyscall = VSYSCALL_RANGE.contains(&start)
+                || mapping.is_some_and(|m| m.file_path == VSYSCALL_PATH);
+
+            let kind = if is_vsyscall {
+                RegionKind::Vsyscall
+            } else if segment.is_some() || mapping.is_some() {
+                RegionKind::Mapped
+            } else {
+                RegionKind::Gap
+            };
+
+            regions.push(AddressRegion {
+                start,
+                end,
+                kind,
+                file_path: mapping.map(|m| m.file_path),
+                flags: segment.map(|s| s.flags),
+                // Regions are split at segment bounds, so the region lies
+                // within the segment, but its file-backed part may end
+                // anywhere inside the region.
+                present: segment.is_some_and(|s| end - s.vm_start <= s.data.len()),
+            });
+        }
+
+        regions
+    }
+}
@@ -0,0 +1,67 @@
+use crate::core::Core;
+use crate::read::ByteOrder;
+
+/// Size of a machine word of the crashed process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum WordSize {
+    /// 64-bit words, from `ELFCLASS64` cores.
+    Bits64,
+}
+
+/// Machine architecture of the crashed process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Architecture {
+    /// x86-64, from `EM_X86_64` cores.
+    X86_64,
+}
+
+/// Byte order of the core file and the crashed process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Core<'_> {
+    /// Return the word size of the crashed process.
+    ///
+    /// Only 64-bit cores are supported at the moment, so this always
+    /// returns [`WordSize::Bits64`].
+    pub fn word_size(&self) -> WordSize {
+        // The ELF class is verified during parsing.
+        WordSize::Bits64
+    }
+
+    /// Return the machine architecture of the crashed process.
+    ///
+    /// Only x86-64 cores are supported at the moment, so this always
+    /// returns [`Architecture::X86_64`].
+    pub fn architecture(&self) -> Architecture {
+        // The ELF machine is verified during parsing.
+        Architecture::X86_64
+    }
+
+    /// Return the raw OS/ABI byte of the ELF header (`e_ident[EI_OSABI]`).
+    ///
+    /// This is either `0` (System V) or `3` (Linux). Cores built with
+    /// [`Core::new`] report `0`.
+    pub fn os_abi(&self) -> u8 {
+        self.elf().data().get(7).copied().unwrap_or(0)
+    }
+
+    /// Return the raw ABI version byte of the ELF header
+    /// (`e_ident[EI_ABIVERSION]`).
+    pub fn abi_version(&self) -> u8 {
+        self.elf().data().get(8).copied().unwrap_or(0)
+    }
+
+    /// Return the byte order of the core file.
+    pub fn endianness(&self) -> Endianness {
+        match self.byte_order() {
+            ByteOrder::Little => Endianness::Little,
+            ByteOrder::Big => Endianness::Big,
+        }
+    }
+}
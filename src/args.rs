@@ -0,0 +1,102 @@
+use crate::core::{Core, CORE_OWNERS};
+use crate::ctypes::{AT_EXECFN, AT_NULL, NT_AUXV};
+use crate::read::ReadExt;
+
+/// A list of NUL-terminated strings read from memory, without their
+/// terminators.
+type Strings<'d> = Vec<&'d [u8]>;
+
+impl<'d> Core<'d> {
+    /// Recover the full argument and environment vectors of the process from
+    /// its initial stack.
+    ///
+    /// The kernel places the `argc`, `argv`, `envp`, and auxiliary vector at
+    /// the top of the initial stack. This locates the stack through the
+    /// `AT_EXECFN` entry of the `NT_AUXV` note, finds the auxiliary vector on
+    /// it, and walks back from there. Unlike [`ProcessInfo::command`], the
+    /// recovered arguments are not truncated.
+    ///
+    /// Returns `None` if the core has no `NT_AUXV` note or the relevant
+    /// stack memory is not present in the core. The results are unreliable
+    /// if the process modified its initial stack.
+    ///
+    /// [`ProcessInfo::command`]: crate::ProcessInfo::command
+    pub fn recover_argv_envp(&self) -> Option<(Strings<'d>, Strings<'d>)> {
+        let auxv = self.auxv_bytes()?;
+        let execfn = self.auxv_value(AT_EXECFN)?;
+        let stack = self.segment_at(execfn as usize)?;
+
+        // The stack contains a copy of the auxiliary vector, which is what
+        // the kernel saved into the `NT_AUXV` note.
+        let auxv_offset = stack
+            .data
+            .chunks_exact(8)
+            .enumerate()
+            .map(|(i, _)| i * 8)
+            .find(|&off| stack.data[off..].starts_with(auxv))?;
+        let auxv_addr = stack.vm_start + auxv_offset;
+
+        // Walk back over the NULL-terminated `envp` array.
+        let mut addr = auxv_addr.checked_sub(16)?;
+        let mut envp = Vec::new();
+        loop {
+            let ptr = self.read_u64_at(addr)?;
+            if ptr == 0 {
+                break;
+            }
+            envp.push(ptr);
+            addr = addr.checked_sub(8)?;
+        }
+
+        // Walk back over the `argv` array until we find `argc`, which equals
+        // the number of pointers seen so far.
+        addr = addr.checked_sub(8)?;
+        let mut argv = Vec::new();
+        loop {
+            let value = self.read_u64_at(addr)?;
+            if value == argv.len() as u64 {
+                break;
+            }
+            argv.push(value);
+            addr = addr.checked_sub(8)?;
+        }
+
+        let read_strings = |ptrs: Vec<u64>| -> Option<Strings<'d>> {
+            ptrs.into_iter()
+                .rev()
+                .map(|p| self.read_c_string_at(p as usize, usize::MAX))
+                .collect()
+        };
+        Some((read_strings(argv)?, read_strings(envp)?))
+    }
+
+    /// Return the `NT_AUXV` note descriptor, up to and including the
+    /// terminating `AT_NULL` entry.
+    fn auxv_bytes(&self) -> Option<&'d [u8]> {
+        let desc = self.elf().get_note(CORE_OWNERS, NT_AUXV)?;
+        let order = self.byte_order();
+        let end = desc
+            .chunks_exact(16)
+            .position(|mut entry| entry.read_u64_with(order).ok() == Some(AT_NULL))?;
+        desc.get(..(end + 1) * 16)
+    }
+
+    /// Return the value of the given entry of the auxiliary vector.
+    pub(crate) fn auxv_value(&self, type_: u64) -> Option<u64> {
+        let mut auxv = self.auxv_bytes()?;
+        let order = self.byte_order();
+        while !auxv.is_empty() {
+            let key = auxv.read_u64_with(order).ok()?;
+            let value = auxv.read_u64_with(order).ok()?;
+            if key == type_ {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn read_u64_at(&self, addr: usize) -> Option<u64> {
+        let mut data = self.read_memory(addr, 8)?;
+        data.read_u64_with(self.byte_order()).ok()
+    }
+}
@@ -1,10 +1,96 @@
-use std::{env, fs};
+use std::{env, fs, process};
 
-use licore::Core;
+use licore::{Core, ParseOptions};
+
+const USAGE: &str = "usage: coredump [--strict] <file> [info | maps | regs [--thread PID]]";
 
 fn main() {
-    let path = env::args().nth(1).expect("no path supplied");
+    let mut args = env::args().skip(1).peekable();
+    let strict = args.next_if(|arg| arg == "--strict").is_some();
+    let path = args.next().unwrap_or_else(|| usage());
+    let command = args.next();
+    let rest: Vec<String> = args.collect();
+
+    // Kernel cores commonly contain segments whose memory size exceeds their
+    // file size, so only reject those if asked to.
+    let options = if strict {
+        ParseOptions::default()
+    } else {
+        ParseOptions {
+            allow_size_mismatch: true,
+            ..Default::default()
+        }
+    };
+
     let data = fs::read(path).expect("error reading core file");
-    let core = Core::parse(&data).expect("error parsing core file");
-    dbg!(core);
+    let core = Core::parse_with_options(&data, options).expect("error parsing core file");
+
+    match command.as_deref() {
+        None => {
+            dbg!(core);
+        }
+        Some("info") => print_info(&core),
+        Some("maps") => print_maps(&core),
+        Some("regs") => {
+            let pid = match rest.as_slice() {
+                [] => None,
+                [flag, pid] if flag == "--thread" => Some(pid.parse().unwrap_or_else(|_| usage())),
+                _ => usage(),
+            };
+            print_regs(&core, pid);
+        }
+        Some(_) => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
+
+fn print_info(core: &Core) {
+    let p = &core.process;
+    println!("file name: {}", String::from_utf8_lossy(p.file_name));
+    println!("command:   {}", String::from_utf8_lossy(p.command));
+    println!("pid:       {}", p.pid);
+    println!("ppid:      {}", p.ppid);
+    println!("pgrp:      {}", p.pgrp);
+    println!("sid:       {}", p.sid);
+    println!("uid:       {}", p.uid);
+    println!("gid:       {}", p.gid);
+    println!("state:     {} ({})", p.state_name, p.state);
+    println!("threads:   {}", core.threads.len());
+}
+
+fn print_maps(core: &Core) {
+    for m in &core.file_map {
+        println!(
+            "{:016x}-{:016x} {:08x} {}",
+            m.vm_start,
+            m.vm_end,
+            m.file_offset,
+            String::from_utf8_lossy(m.file_path),
+        );
+    }
+}
+
+/// Print the registers of the given thread, or of the crashing thread if no
+/// pid is given.
+///
+/// The kernel writes the crashing thread's status first, so that's the one
+/// we pick by default.
+fn print_regs(core: &Core, pid: Option<i32>) {
+    let thread = match pid {
+        Some(pid) => core.threads.iter().find(|t| t.pid == pid),
+        None => core.threads.first(),
+    };
+    let Some(thread) = thread else {
+        eprintln!("no such thread");
+        process::exit(1);
+    };
+
+    println!("thread {}", thread.pid);
+    for (name, value) in thread.registers.iter() {
+        println!("{:>8} {value:#018x}", name.as_str());
+    }
 }
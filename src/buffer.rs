@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+use crate::core::{Core, SegmentIndex};
+use crate::error::ParseError;
+use crate::options::ParseOptions;
+use crate::owned::OwnedCore;
+
+/// A parsed core file together with its data, which is either borrowed or
+/// owned.
+///
+/// A [`Core`] borrows the data it was parsed from, so it cannot be stored
+/// together with an owned buffer, e.g. one returned by
+/// [`decompress`](crate::decompress). A `CoreBuffer` instead keeps the
+/// metadata of the core as an [`OwnedCore`] alongside the buffer, together
+/// with the location of each segment's data in the buffer. The core is
+/// parsed only once, when the `CoreBuffer` is created.
+///
+/// To get a full [`Core`] borrowing from the buffer, parse
+/// [`CoreBuffer::data`] with [`Core::parse_with_options`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CoreBuffer<'d> {
+    data: Cow<'d, [u8]>,
+    core: OwnedCore,
+    /// The byte range of each segment's data in `data`, in the order of
+    /// `core.segments`.
+    segment_data: Vec<Range<usize>>,
+    /// Lookup table of the address ranges of `core.segments`.
+    segment_index: SegmentIndex,
+}
+
+impl<'d> CoreBuffer<'d> {
+    /// Parse the given core file data with the given options, and store the
+    /// result together with the data.
+    pub fn new(data: impl Into<Cow<'d, [u8]>>, options: ParseOptions) -> Result<Self, ParseError> {
+        let data = data.into();
+        let (core, segment_data) = {
+            let core = Core::parse_with_options(&data, options)?;
+            let base = data.as_ptr() as usize;
+            let segment_data = core
+                .segments
+                .iter()
+                .map(|s| {
+                    // Empty segment data need not point into `data`.
+                    if s.data.is_empty() {
+                        return 0..0;
+                    }
+                    let start = s.data.as_ptr() as usize - base;
+                    start..start + s.data.len()
+                })
+                .collect();
+            (core.into_owned(), segment_data)
+        };
+        let segment_index = SegmentIndex::new(core.segments.iter().map(|s| s.vm_start..s.vm_end));
+
+        Ok(Self {
+            data,
+            core,
+            segment_data,
+            segment_index,
+        })
+    }
+
+    /// Return the parsed core metadata.
+    pub fn core(&self) -> &OwnedCore {
+        &self.core
+    }
+
+    /// Return the data of the segment at the given position in
+    /// [`OwnedCore::segments`].
+    pub fn segment_data(&self, index: usize) -> Option<&[u8]> {
+        let range = self.segment_data.get(index)?;
+        self.data.get(range.clone())
+    }
+
+    /// Read `len` bytes of memory starting at the given virtual address.
+    ///
+    /// Like [`Core::read_memory`], this returns `None` if the range is not
+    /// fully contained in a single segment or its bytes are not present in
+    /// the core file.
+    pub fn read_memory(&self, addr: usize, len: usize) -> Option<&[u8]> {
+        let index = self.segment_index.position_of(addr)?;
+        let start = addr - self.core.segments[index].vm_start;
+        let end = start.checked_add(len)?;
+        self.segment_data(index)?.get(start..end)
+    }
+
+    /// Return the core file data.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return the core file data, dropping the parsed metadata.
+    pub fn into_data(self) -> Cow<'d, [u8]> {
+        self.data
+    }
+}
+
+impl fmt::Debug for CoreBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoreBuffer")
+            .field("data", &format_args!("[{} bytes]", self.data.len()))
+            .field("core", &self.core)
+            .finish()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::core::Core;
+use crate::ctypes::{
+    GNU_PROPERTY_X86_FEATURE_1_AND, GNU_PROPERTY_X86_FEATURE_1_IBT,
+    GNU_PROPERTY_X86_FEATURE_1_SHSTK, NT_GNU_PROPERTY_TYPE_0,
+};
+use crate::note::GNU;
+use crate::read::{ByteOrder, ReadExt};
+
+/// The x86 control-flow enforcement features enabled for the process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CetFeatures {
+    /// Indirect branch tracking.
+    pub ibt: bool,
+    /// Shadow stack.
+    pub shstk: bool,
+}
+
+impl<'d> Core<'d> {
+    /// Return the control-flow enforcement features recorded in the
+    /// `GNU`/`NT_GNU_PROPERTY_TYPE_0` note.
+    ///
+    /// Returns `None` if the core has no such note, or if the note does not
+    /// contain a `GNU_PROPERTY_X86_FEATURE_1_AND` property.
+    pub fn cet_features(&self) -> Option<CetFeatures> {
+        let desc = self.elf().get_note(&[GNU], NT_GNU_PROPERTY_TYPE_0)?;
+        let bits = find_feature_1_and(desc, self.byte_order())?;
+
+        Some(CetFeatures {
+            ibt: bits & GNU_PROPERTY_X86_FEATURE_1_IBT != 0,
+            shstk: bits & GNU_PROPERTY_X86_FEATURE_1_SHSTK != 0,
+        })
+    }
+}
+
+/// Find the `GNU_PROPERTY_X86_FEATURE_1_AND` bits in a program property
+/// note descriptor.
+fn find_feature_1_and(mut data: &[u8], order: ByteOrder) -> Option<u32> {
+    while !data.is_empty() {
+        let type_ = data.read_u32_with(order).ok()?;
+        let size = data.read_u32_with(order).ok()? as usize;
+        let mut value = data.read_slice(size).ok()?;
+
+        if type_ == GNU_PROPERTY_X86_FEATURE_1_AND {
+            return value.read_u32_with(order).ok();
+        }
+
+        // Properties are padded to 8 bytes in 64-bit objects.
+        let padding = (8 - size % 8) % 8;
+        data = data.get(padding..)?;
+    }
+
+    None
+}
@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+
+use crate::error::ParseError;
+use crate::options::ParseOptions;
+use crate::read::ReadExt;
+
+/// Magic bytes at the start of xz streams.
+const XZ_MAGIC: &[u8] = b"\xfd7zXZ\x00";
+/// Magic number at the start of lz4 frames, stored little-endian.
+const LZ4_MAGIC: u32 = 0x184d_2204;
+/// Bit in the lz4 frame descriptor flags that is set if the frame header
+/// contains the decompressed size.
+const LZ4_CONTENT_SIZE_FLAG: u8 = 0x08;
+
+/// Decompress a core file, detecting the compression format from its magic
+/// bytes.
+///
+/// This supports the formats `systemd-coredump` uses to store cores: xz
+/// with the `xz` feature, and lz4 with the `lz4` feature. Data in any other
+/// format, including uncompressed cores, is returned unchanged. Since
+/// [`Core`] borrows its input, the decompressed data is returned for the
+/// caller to keep alive and pass to [`Core::parse`].
+///
+/// The decompressed size is limited by the default
+/// [`ParseOptions::max_decompressed_size`].
+///
+/// [`Core`]: crate::Core
+/// [`Core::parse`]: crate::Core::parse
+pub fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>, ParseError> {
+    decompress_with_options(data, ParseOptions::default())
+}
+
+/// Decompress a core file, like [`decompress`], with the given options.
+///
+/// Decompression fails once the output exceeds
+/// [`ParseOptions::max_decompressed_size`].
+pub fn decompress_with_options(
+    data: &[u8],
+    options: ParseOptions,
+) -> Result<Cow<'_, [u8]>, ParseError> {
+    let limit = options.max_decompressed_size;
+    if data.starts_with(XZ_MAGIC) {
+        return decompress_xz(data, limit).map(Cow::Owned);
+    }
+    let mut magic = data;
+    if magic.read_u32() == Ok(LZ4_MAGIC) {
+        if lz4_content_size(data).is_some_and(|size| size > limit) {
+            return Err(ParseError::DecompressedSizeExceeded { limit });
+        }
+        return decompress_lz4(data, limit).map(Cow::Owned);
+    }
+
+    Ok(Cow::Borrowed(data))
+}
+
+/// Return the decompressed size recorded in the header of the lz4 frame at
+/// the start of `data`, if the header includes it.
+fn lz4_content_size(mut data: &[u8]) -> Option<u64> {
+    let _magic = data.read_u32().ok()?;
+    let [flags, _block_descriptor] = data.read_owned_array().ok()?;
+    if flags & LZ4_CONTENT_SIZE_FLAG == 0 {
+        return None;
+    }
+    data.read_u64().ok()
+}
+
+#[cfg(feature = "xz")]
+fn decompress_xz(mut data: &[u8], limit: u64) -> Result<Vec<u8>, ParseError> {
+    let mut out = LimitedWriter {
+        buf: Vec::new(),
+        limit,
+        exceeded: false,
+    };
+    let result = lzma_rs::xz_decompress(&mut data, &mut out);
+    if out.exceeded {
+        return Err(ParseError::DecompressedSizeExceeded { limit });
+    }
+    result.map_err(|e| ParseError::Decompression {
+        format: "xz",
+        reason: e.to_string(),
+    })?;
+    Ok(out.buf)
+}
+
+#[cfg(not(feature = "xz"))]
+fn decompress_xz(_data: &[u8], _limit: u64) -> Result<Vec<u8>, ParseError> {
+    Err(ParseError::CompressionUnsupported { format: "xz" })
+}
+
+/// A writer into a vector that fails instead of growing the vector beyond
+/// `limit` bytes.
+#[cfg(feature = "xz")]
+struct LimitedWriter {
+    buf: Vec<u8>,
+    limit: u64,
+    exceeded: bool,
+}
+
+#[cfg(feature = "xz")]
+impl std::io::Write for LimitedWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        if self.buf.len() as u64 + data.len() as u64 > self.limit {
+            self.exceeded = true;
+            return Err(std::io::Error::other("size limit exceeded"));
+        }
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lz4")]
+fn decompress_lz4(data: &[u8], limit: u64) -> Result<Vec<u8>, ParseError> {
+    use std::io::Read;
+
+    // Read one byte past the limit to tell whether it is exceeded.
+    let mut out = Vec::new();
+    lz4_flex::frame::FrameDecoder::new(data)
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut out)
+        .map_err(|e| ParseError::Decompression {
+            format: "lz4",
+            reason: e.to_string(),
+        })?;
+    if out.len() as u64 > limit {
+        return Err(ParseError::DecompressedSizeExceeded { limit });
+    }
+    Ok(out)
+}
+
+#[cfg(not(feature = "lz4"))]
+fn decompress_lz4(_data: &[u8], _limit: u64) -> Result<Vec<u8>, ParseError> {
+    Err(ParseError::CompressionUnsupported { format: "lz4" })
+}
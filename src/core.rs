@@ -1,52 +1,935 @@
 use core::fmt;
+use std::borrow::Cow;
+use std::cmp;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::mem;
+use std::ops::{ControlFlow, Range};
+use std::time::Duration;
+
+use structview::View;
 
 use crate::ctypes::{
-    elf_gregset_t, elf_prpsinfo, elf_prstatus, CType, NT_FILE, NT_PRPSINFO, NT_PRSTATUS, PT_LOAD,
+    __kernel_old_timeval, elf_gregset_t, elf_prpsinfo, elf_prstatus, siginfo_t, CType,
+    NT_386_IOPERM, NT_AUXV, NT_FILE, NT_GDB_TDESC, NT_GNU_PROPERTY_TYPE_0, NT_PRPSINFO,
+    NT_PRSTATUS, NT_SIGINFO, PF_R, PF_W, PF_X, PT_LOAD,
 };
-use crate::elf::Elf;
-use crate::error::ParseError;
-use crate::read::ReadExt;
+use crate::elf::{Elf, Note};
+use crate::error::{ParseError, ParseWarning};
+use crate::note::{CustomNote, NoteRegistry, NoteSummary, RawNote, CORE, GDB, GNU, LINUX};
+use crate::options::ParseOptions;
+use crate::read::{self, with_byte_order, ByteOrder, ReadExt};
+use crate::signal::{SignalInfo, SignalSet, SignalState};
 use crate::util::trim_c_string;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Owner names accepted for the standard core file notes.
+///
+/// The kernel writes these notes with the owner name `CORE`, but some tools
+/// use `LINUX` instead.
+pub(crate) const CORE_OWNERS: &[&[u8]] = &[CORE, LINUX];
+
+/// Upper bound on the size of a file reconstructed by
+/// [`Core::reconstruct_file`], in bytes.
+pub const MAX_RECONSTRUCTED_FILE_SIZE: usize = 1 << 30;
+
+/// Return whether notes with the given owner name and type are interpreted
+/// by this crate.
+fn is_handled_note(name: &[u8], type_: u32) -> bool {
+    let core_note = CORE_OWNERS.contains(&name)
+        && [NT_PRSTATUS, NT_PRPSINFO, NT_FILE, NT_SIGINFO, NT_AUXV].contains(&type_);
+    core_note
+        || (name, type_) == (LINUX, NT_386_IOPERM)
+        || (name, type_) == (GDB, NT_GDB_TDESC)
+        || (name, type_) == (GNU, NT_GNU_PROPERTY_TYPE_0)
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Core<'d> {
+    /// The loadable segments of the core file, in file order unless sorted
+    /// with [`Core::sort_segments_by_address`].
+    ///
+    /// This may be empty, e.g. for cores written with a `coredump_filter` of
+    /// zero, which contain only notes. Memory reads then fail gracefully.
+    ///
+    /// Address lookups use an index of the segment address ranges built
+    /// during parsing. After modifying this list, call
+    /// [`Core::rebuild_segment_index`]. Until then, lookups ignore segments
+    /// whose address range changed, and don't find added segments.
     pub segments: Vec<Segment<'d>>,
     pub process: ProcessInfo<'d>,
-    pub threads: Vec<ThreadInfo>,
+    pub threads: Vec<ThreadInfo<'d>>,
     pub file_map: Vec<FileMapping<'d>>,
+    pub signal: Option<SignalInfo>,
+    /// The notes decoded by the registry passed to
+    /// [`Core::parse_with_registry`], in the order of [`Core::extra_notes`].
+    pub custom_notes: Vec<CustomNote<'d>>,
+    elf: Elf<'d>,
+    /// Lookup table of the address ranges of `segments`.
+    segment_index: SegmentIndex,
+    /// Notes not interpreted by this crate.
+    extra_notes: Vec<RawNote<'d>>,
+    /// Descriptor of the full command line note, if present.
+    cmdline: Option<&'d [u8]>,
+    /// Non-fatal problems encountered during parsing.
+    warnings: Vec<ParseWarning>,
 }
 
 impl<'d> Core<'d> {
+    /// Parse a core file.
+    ///
+    /// The standard notes (`NT_PRPSINFO`, `NT_PRSTATUS`, `NT_FILE`) are
+    /// accepted with either the `CORE` or the `LINUX` owner name.
     pub fn parse(data: &'d [u8]) -> Result<Self, ParseError> {
-        let elf = Elf::parse(data)?;
+        Self::parse_with_options(data, ParseOptions::default())
+    }
+
+    /// Parse a core file, validating it as strictly as the given options
+    /// require.
+    pub fn parse_with_options(data: &'d [u8], options: ParseOptions) -> Result<Self, ParseError> {
+        let elf = Elf::parse(data, &options)?;
+        let mut warnings = elf.warnings().to_vec();
+        let summary = CoreSummary::extract(&elf, &options, &mut warnings)?;
+        let segments = extract_segments(&elf, &options, &mut warnings)?;
+
+        let segment_index = SegmentIndex::new(segments.iter().map(Segment::range));
+        warnings.extend(find_overlapping_segments(&segments, &segment_index));
+        let cmdline = elf.get_note(CORE_OWNERS, options.cmdline_note_type);
+        let is_option_note = |n: &Note| {
+            CORE_OWNERS.contains(&n.name)
+                && (n.type_ == options.cmdline_note_type
+                    || Some(n.type_) == options.debug_registers_note_type)
+        };
+
+        let extra_notes = elf
+            .notes()
+            .iter()
+            .filter(|n| !is_handled_note(n.name, n.type_) && !is_option_note(n))
+            .map(Note::to_raw)
+            .collect();
 
         Ok(Self {
-            segments: extract_segments(&elf)?,
-            process: extract_process_info(&elf)?,
-            threads: extract_thread_infos(&elf)?,
-            file_map: extract_file_map(&elf)?,
+            segments,
+            process: summary.process,
+            threads: summary.threads,
+            file_map: summary.file_map,
+            signal: summary.signal,
+            custom_notes: Vec::new(),
+            elf,
+            segment_index,
+            extra_notes,
+            cmdline,
+            warnings,
         })
     }
+
+    /// Build a core from the given parts, e.g. to test code consuming cores.
+    ///
+    /// The core is not backed by a file: it has no notes besides the given
+    /// ones, no warnings, and [`Core::bytes_at_file_offset`] always returns
+    /// `None`. It is treated as a little-endian x86-64 core.
+    pub fn new(
+        segments: Vec<Segment<'d>>,
+        process: ProcessInfo<'d>,
+        threads: Vec<ThreadInfo<'d>>,
+        file_map: Vec<FileMapping<'d>>,
+        signal: Option<SignalInfo>,
+    ) -> Self {
+        let segment_index = SegmentIndex::new(segments.iter().map(Segment::range));
+        Self {
+            segments,
+            process,
+            threads,
+            file_map,
+            signal,
+            custom_notes: Vec::new(),
+            elf: Elf::empty(),
+            segment_index,
+            extra_notes: Vec::new(),
+            cmdline: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Parse only the metadata of a core file, skipping segment extraction.
+    ///
+    /// This is considerably cheaper than [`Core::parse`] for large cores, as
+    /// the contents of loadable segments are never touched.
+    pub fn parse_header_only(data: &'d [u8]) -> Result<CoreSummary<'d>, ParseError> {
+        let options = ParseOptions::default();
+        let elf = Elf::parse(data, &options)?;
+        CoreSummary::extract(&elf, &options, &mut Vec::new())
+    }
+
+    /// Parse a core file like [`Core::parse`], and decode the notes not
+    /// interpreted by this crate with the decoders of the given registry.
+    ///
+    /// The decoded notes are stored in [`Core::custom_notes`]. Notes without
+    /// a matching decoder are skipped.
+    pub fn parse_with_registry(
+        data: &'d [u8],
+        registry: &NoteRegistry,
+    ) -> Result<Self, ParseError> {
+        let mut core = Self::parse(data)?;
+        core.custom_notes = core
+            .extra_notes
+            .iter()
+            .filter_map(|note| registry.decode(note))
+            .collect();
+        Ok(core)
+    }
+
+    /// Parse a core file from any buffer that can be viewed as bytes.
+    ///
+    /// This is a convenience wrapper around [`Core::parse`] for types like
+    /// `Vec<u8>` or memory maps.
+    pub fn parse_from<D: AsRef<[u8]> + ?Sized>(data: &'d D) -> Result<Self, ParseError> {
+        Self::parse(data.as_ref())
+    }
+
+    /// Return the XML target description embedded by GDB, if present.
+    ///
+    /// The description is found in the `GDB`/`NT_GDB_TDESC` note and
+    /// returned as raw bytes.
+    pub fn target_description(&self) -> Option<&'d [u8]> {
+        self.elf.get_note(&[GDB], NT_GDB_TDESC)
+    }
+
+    /// Return an inventory of all notes in this core, grouped by owner name
+    /// and type and sorted by name, then type.
+    pub fn note_summary(&self) -> Vec<NoteSummary<'d>> {
+        let mut summary: Vec<NoteSummary<'d>> = Vec::new();
+        for note in self.elf.notes() {
+            let entry = summary
+                .iter_mut()
+                .find(|s| s.name == note.name && s.type_ == note.type_);
+            match entry {
+                Some(entry) => {
+                    entry.count += 1;
+                    entry.total_bytes += note.desc.len();
+                }
+                None => summary.push(NoteSummary {
+                    name: note.name,
+                    type_: note.type_,
+                    count: 1,
+                    total_bytes: note.desc.len(),
+                }),
+            }
+        }
+
+        summary.sort_by(|a, b| (a.name, a.type_).cmp(&(b.name, b.type_)));
+        summary
+    }
+
+    /// Return the non-fatal problems encountered while parsing.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Return the notes that are not interpreted by this crate, in the order
+    /// described at [`Core::visit_notes`].
+    pub fn extra_notes(&self) -> &[RawNote<'d>] {
+        &self.extra_notes
+    }
+
+    /// Call `f` for each note of the core, until it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// Notes are visited in the order of the `PT_NOTE` program headers, and
+    /// in file order within each segment. They are followed by the notes of
+    /// `SHT_NOTE` sections not covered by a `PT_NOTE` segment, in section
+    /// header order. Duplicate notes are only dropped if
+    /// [`ParseOptions::dedup_notes`] is set.
+    ///
+    /// Unlike [`Core::extra_notes`] and [`Core::notes_by`], this covers all
+    /// notes, including those interpreted by this crate, and allows stopping
+    /// early once the wanted notes have been found.
+    pub fn visit_notes<F>(&self, f: F) -> ControlFlow<()>
+    where
+        F: FnMut(&RawNote<'d>) -> ControlFlow<()>,
+    {
+        self.elf.visit_notes(f)
+    }
+
+    /// Return the descriptors of all notes with the given owner name and
+    /// type, in the order described at [`Core::visit_notes`].
+    ///
+    /// This gives access to notes that this crate does not interpret. See
+    /// the [`note`](crate::note) module for known owner names and types.
+    pub fn notes_by<'a>(
+        &'a self,
+        name: &'a [u8],
+        type_: u32,
+    ) -> impl Iterator<Item = &'d [u8]> + 'a {
+        self.elf
+            .notes()
+            .iter()
+            .filter(move |n| n.name == name && n.type_ == type_)
+            .map(|n| n.desc)
+    }
+
+    /// Return the full command line of the process, split into its
+    /// arguments.
+    ///
+    /// The arguments are taken from the `CORE` note with the type configured
+    /// in [`ParseOptions::cmdline_note_type`], which some dumpers add to
+    /// record the untruncated command line. If the core has no such note,
+    /// this falls back to [`ProcessInfo::args`], which is truncated.
+    ///
+    /// Returns `None` if neither source provides any arguments.
+    pub fn full_command_line(&self) -> Option<Vec<&'d [u8]>> {
+        let args = match self.cmdline {
+            // Each argument is terminated by a NUL, including the last one.
+            Some(cmdline) => match cmdline.strip_suffix(b"\0").unwrap_or(cmdline) {
+                [] => Vec::new(),
+                cmdline => cmdline.split(|&b| b == 0).collect(),
+            },
+            None => self.process.args(),
+        };
+
+        (!args.is_empty()).then_some(args)
+    }
+
+    /// Return whether the crashed process had more than one thread.
+    pub fn is_multithreaded(&self) -> bool {
+        self.threads.len() > 1
+    }
+
+    /// Return the main thread of the crashed process.
+    ///
+    /// On Linux, the thread ID of the main thread equals the process ID.
+    /// Returns `None` if the core contains no such thread.
+    pub fn main_thread(&self) -> Option<&ThreadInfo<'d>> {
+        self.threads.iter().find(|t| t.pid == self.process.pid)
+    }
+
+    /// Return an iterator over all threads other than the main thread.
+    pub fn worker_threads(&self) -> impl Iterator<Item = &ThreadInfo<'d>> {
+        self.threads.iter().filter(|t| t.pid != self.process.pid)
+    }
+
+    /// Return the threads of this core, with the main thread first and the
+    /// remaining threads sorted by pid.
+    ///
+    /// The main thread is the one whose pid equals the process pid.
+    pub fn threads_ordered(&self) -> Vec<&ThreadInfo<'d>> {
+        let mut threads: Vec<_> = self.threads.iter().collect();
+        threads.sort_by_key(|t| (t.pid != self.process.pid, t.pid));
+        threads
+    }
+
+    /// Return the thread that used the most CPU time.
+    ///
+    /// If several threads used the same amount of CPU time, the first of them
+    /// is returned.
+    pub fn busiest_thread(&self) -> Option<&ThreadInfo<'d>> {
+        self.threads.iter().rev().max_by_key(|t| t.total_cpu_time())
+    }
+
+    /// Return the PID of each thread, paired with the address range of the
+    /// segment containing its stack pointer.
+    ///
+    /// The range is `None` if the stack pointer does not point into any
+    /// segment, e.g. for kernel threads or corrupted registers. Threads are
+    /// returned in the order of [`Core::threads`].
+    pub fn thread_stacks(&self) -> Vec<(i32, Option<Range<usize>>)> {
+        self.threads
+            .iter()
+            .map(|t| {
+                let sp = t.registers.stack_pointer() as usize;
+                (t.pid, self.segment_at(sp).map(Segment::range))
+            })
+            .collect()
+    }
+
+    /// Return the bytes of the given thread's TLS block, starting at its
+    /// thread pointer `fs_base` and extending to the end of the segment's
+    /// data present in the core.
+    ///
+    /// With glibc, the thread pointer points to the thread's `tcbhead_t`,
+    /// which is preceded by the static TLS blocks of the loaded modules and
+    /// followed by the rest of the `struct pthread`. Returns `None` if
+    /// `fs_base` does not point into the data of any segment.
+    pub fn tls_block(&self, thread: &ThreadInfo) -> Option<&'d [u8]> {
+        let tp = thread.registers.fs_base as usize;
+        let segment = self.segment_at(tp)?;
+        segment
+            .data
+            .get(tp - segment.vm_start..)
+            .filter(|d| !d.is_empty())
+    }
+
+    /// Return whether the given thread's thread pointer points to a valid
+    /// glibc `tcbhead_t`.
+    ///
+    /// The first field of `tcbhead_t` points to the TCB itself, so this
+    /// checks that the word at `fs_base` equals `fs_base`. This can fail for
+    /// processes not using glibc, or if the TCB was not dumped.
+    pub fn has_valid_tcb(&self, thread: &ThreadInfo) -> bool {
+        let tp = thread.registers.fs_base;
+        tp != 0 && self.read_u64_at(tp as usize) == Some(tp)
+    }
+
+    pub(crate) fn elf(&self) -> &Elf<'d> {
+        &self.elf
+    }
+
+    pub(crate) fn byte_order(&self) -> ByteOrder {
+        self.elf.byte_order()
+    }
+
+    /// Return the segment with the given identity.
+    pub fn segment(&self, id: SegmentId) -> Option<&Segment<'d>> {
+        self.segments.iter().find(|s| s.id == id)
+    }
+
+    /// Return an iterator over the executable segments.
+    pub fn executable_segments(&self) -> impl Iterator<Item = &Segment<'d>> {
+        self.segments.iter().filter(|s| s.flags.is_executable())
+    }
+
+    /// Return an iterator over the writable segments.
+    pub fn writable_segments(&self) -> impl Iterator<Item = &Segment<'d>> {
+        self.segments.iter().filter(|s| s.flags.is_writable())
+    }
+
+    /// Return an iterator over the segments that overlap the given virtual
+    /// address range.
+    pub fn segments_in_range(&self, range: Range<usize>) -> impl Iterator<Item = &Segment<'d>> {
+        self.segments
+            .iter()
+            .filter(move |s| s.vm_start < range.end && range.start < s.vm_end)
+    }
+
+    /// Return the segments that overlap the given virtual address range,
+    /// sorted by address.
+    ///
+    /// Unlike [`Core::segments_in_range`], this uses the segment index
+    /// rather than checking every segment. Segments that merely touch the
+    /// range, i.e. end at its start or start at its end, are not included.
+    /// Neither are segments with an empty address range. If segments overlap
+    /// each other, all of them that overlap the range are returned.
+    pub fn segments_overlapping(&self, range: Range<usize>) -> Vec<&Segment<'d>> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+
+        self.index_candidates(range.start, range.end)
+            .filter(|s| s.vm_end > range.start)
+            .collect()
+    }
+
+    /// Return the segment containing the given virtual address.
+    ///
+    /// If segments overlap, the one with the highest start address wins.
+    pub fn segment_at(&self, addr: usize) -> Option<&Segment<'d>> {
+        let end = addr.checked_add(1)?;
+        self.index_candidates(addr, end)
+            .rev()
+            .find(|s| s.contains(addr))
+    }
+
+    /// Return the indexed segments that start before `end` and might end
+    /// after `start`, sorted by address.
+    ///
+    /// See [`SegmentIndex::candidates`]. Segments whose address range
+    /// differs from the indexed one are skipped, as the index is stale.
+    fn index_candidates(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl DoubleEndedIterator<Item = &Segment<'d>> {
+        self.segment_index.candidates(start, end).filter_map(|e| {
+            let segment = self.segments.get(e.index)?;
+            (segment.range() == e.range()).then_some(segment)
+        })
+    }
+
+    /// Sort the segments by address, as given by their [`Ord`] impl.
+    ///
+    /// The kernel already writes segments in address order, but other
+    /// dumpers might not.
+    pub fn sort_segments_by_address(&mut self) {
+        self.segments.sort_unstable();
+        self.rebuild_segment_index();
+    }
+
+    /// Rebuild the index used for address lookups from [`Core::segments`].
+    ///
+    /// This must be called after modifying the segments for lookups like
+    /// [`Core::segment_at`] to observe the modifications.
+    pub fn rebuild_segment_index(&mut self) {
+        self.segment_index = SegmentIndex::new(self.segments.iter().map(Segment::range));
+    }
+
+    /// Sort the file mappings by address.
+    pub fn sort_file_map_by_address(&mut self) {
+        self.file_map
+            .sort_unstable_by_key(|m| (m.vm_start, m.vm_end));
+    }
+
+    /// Return `len` bytes of the core file starting at the given file offset.
+    ///
+    /// Unlike [`Core::read_memory`], this addresses the raw ELF file rather
+    /// than the virtual address space of the process. Returns `None` if the
+    /// range extends past the end of the file.
+    pub fn bytes_at_file_offset(&self, offset: usize, len: usize) -> Option<&'d [u8]> {
+        let end = offset.checked_add(len)?;
+        self.elf.data().get(offset..end)
+    }
+
+    /// Return the memory permissions at the given virtual address.
+    ///
+    /// Returns `None` if the address is not covered by a segment.
+    pub fn permissions_at(&self, addr: usize) -> Option<SegmentFlags> {
+        self.segment_at(addr).map(|s| s.flags)
+    }
+
+    /// Read `len` bytes of memory starting at the given virtual address.
+    ///
+    /// Returns `None` if the range is not fully contained in a single segment
+    /// or its bytes are not present in the core file. Use
+    /// [`Core::read_memory_spanning`] to read ranges that cross segment
+    /// boundaries.
+    ///
+    /// Reads from the vsyscall page (see
+    /// [`RegionKind::Vsyscall`](crate::RegionKind::Vsyscall)) return `None`
+    /// if the kernel did not dump it, and zeros if the page is execute-only.
+    pub fn read_memory(&self, addr: usize, len: usize) -> Option<&'d [u8]> {
+        let segment = self.segment_at(addr)?;
+        let start = addr - segment.vm_start;
+        let end = start.checked_add(len)?;
+        segment.data.get(start..end)
+    }
+
+    /// Read `len` bytes of memory starting at the given virtual address,
+    /// stitching together bytes from adjacent segments.
+    ///
+    /// Returns `None` if any part of the range is not covered by a segment.
+    pub fn read_memory_spanning(&self, addr: usize, len: usize) -> Option<Vec<u8>> {
+        let end = addr.checked_add(len)?;
+
+        // `len` is untrusted, so the buffer only grows as data is copied.
+        let mut bytes = Vec::new();
+        let mut cur = addr;
+        while cur < end {
+            let segment = self.segment_at(cur)?;
+            let count = segment.vm_end.min(end) - cur;
+            let data = self.read_memory(cur, count)?;
+            bytes.extend_from_slice(data);
+            cur += count;
+        }
+
+        Some(bytes)
+    }
+
+    /// Interpret the memory at the given virtual address as a `T`.
+    ///
+    /// Returns `None` if the memory occupied by the `T` is not fully
+    /// contained in a single segment.
+    pub fn read_struct<T: View>(&self, addr: usize) -> Option<&'d T> {
+        let data = self.read_memory(addr, mem::size_of::<T>())?;
+        T::view(data).ok()
+    }
+
+    /// Reconstruct the contents of a mapped file from the memory in this core.
+    ///
+    /// The bytes of all mappings of the file at `path` are copied to their
+    /// respective file offsets. Only memory actually present in the dump is
+    /// recovered; gaps are filled with zeros. The buffer ends with the last
+    /// recovered byte, so it may be shorter than the original file.
+    ///
+    /// To bound the buffer size for corrupt `NT_FILE` offsets, bytes at file
+    /// offsets of [`MAX_RECONSTRUCTED_FILE_SIZE`] or more are not recovered.
+    pub fn reconstruct_file(&self, path: &[u8]) -> Vec<u8> {
+        let mut mappings: Vec<_> = self
+            .file_map
+            .iter()
+            .filter(|m| m.file_path == path)
+            .collect();
+        mappings.sort_by_key(|m| m.file_offset);
+
+        // Collect the recovered chunks first, to allocate the buffer once.
+        let mut chunks = Vec::new();
+        for mapping in mappings {
+            for segment in &self.segments {
+                let start = segment.vm_start.max(mapping.vm_start);
+                let end = segment.vm_end.min(mapping.vm_end);
+                if start >= end {
+                    continue;
+                }
+
+                // Segments may contain fewer bytes than they span.
+                let data_start = start - segment.vm_start;
+                let data_end = (end - segment.vm_start).min(segment.data.len());
+                let Some(data) = segment.data.get(data_start..data_end) else {
+                    continue;
+                };
+
+                let Some(offset) = usize::try_from(mapping.file_offset)
+                    .ok()
+                    .and_then(|o| o.checked_add(start - mapping.vm_start))
+                    .filter(|&o| o < MAX_RECONSTRUCTED_FILE_SIZE)
+                else {
+                    continue;
+                };
+                let len = data.len().min(MAX_RECONSTRUCTED_FILE_SIZE - offset);
+                chunks.push((offset, &data[..len]));
+            }
+        }
+
+        let len = chunks.iter().map(|(o, d)| o + d.len()).max().unwrap_or(0);
+        let mut buffer = vec![0; len];
+        for (offset, data) in chunks {
+            buffer[offset..][..data.len()].copy_from_slice(data);
+        }
+        buffer
+    }
+
+    /// Read a NUL-terminated string starting at the given virtual address.
+    ///
+    /// The returned bytes exclude the terminator. Reading stops after
+    /// `max_len` bytes or at the end of the containing segment, whichever
+    /// comes first. Returns `None` if the address is not mapped.
+    pub fn read_c_string_at(&self, addr: usize, max_len: usize) -> Option<&'d [u8]> {
+        let segment = self.segment_at(addr)?;
+        let data = segment.data.get(addr - segment.vm_start..)?;
+        let data = &data[..data.len().min(max_len)];
+        Some(trim_c_string(data))
+    }
+
+    /// Return the number of segment bytes actually present in the core file.
+    pub fn total_file_backed_bytes(&self) -> u64 {
+        self.segments.iter().map(|s| s.data.len() as u64).sum()
+    }
+
+    /// Return the number of bytes of virtual memory spanned by all segments.
+    ///
+    /// Together with [`Core::total_file_backed_bytes`] this can be used to
+    /// determine how sparse a core file is.
+    pub fn total_mapped_bytes(&self) -> u64 {
+        self.segments.iter().map(|s| s.len() as u64).sum()
+    }
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Return a warning for each pair of segments whose memory ranges overlap.
+///
+/// Only segments that are adjacent in the address-sorted `index` are compared.
+fn find_overlapping_segments(segments: &[Segment], index: &SegmentIndex) -> Vec<ParseWarning> {
+    index
+        .entries
+        .windows(2)
+        .filter(|pair| pair[0].vm_end > pair[1].vm_start)
+        .map(|pair| ParseWarning::OverlappingSegments {
+            first: segments[pair[0].index].id,
+            second: segments[pair[1].index].id,
+        })
+        .collect()
+}
+
+/// A lookup table of segment address ranges, sorted by address.
+///
+/// The table records the address ranges themselves, so lookups don't depend
+/// on the segment list it was built from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct SegmentIndex {
+    entries: Vec<SegmentIndexEntry>,
+}
+
+/// An entry of the segment lookup table.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct SegmentIndexEntry {
+    vm_start: usize,
+    vm_end: usize,
+    /// The highest end address of this and all preceding entries.
+    ///
+    /// Unlike the end addresses of overlapping segments, this is sorted, so
+    /// it can be binary searched.
+    max_end: usize,
+    /// The position of the segment in the list the index was built from.
+    pub index: usize,
+}
+
+impl SegmentIndexEntry {
+    /// Return the indexed address range.
+    pub fn range(&self) -> Range<usize> {
+        self.vm_start..self.vm_end
+    }
+}
+
+impl SegmentIndex {
+    /// Build the lookup table for the given segment address ranges.
+    pub fn new(ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        // Empty segments can't contain any address, so they are left out of
+        // the index.
+        let mut entries: Vec<_> = ranges
+            .into_iter()
+            .enumerate()
+            .filter(|(_, r)| !r.is_empty())
+            .map(|(i, r)| SegmentIndexEntry {
+                vm_start: r.start,
+                vm_end: r.end,
+                max_end: r.end,
+                index: i,
+            })
+            .collect();
+        entries.sort_unstable();
+
+        let mut max_end = 0;
+        for entry in &mut entries {
+            max_end = max_end.max(entry.max_end);
+            entry.max_end = max_end;
+        }
+        Self { entries }
+    }
+
+    /// Return the entries that start before `end` and might end after
+    /// `start`, sorted by address.
+    ///
+    /// Segments in cores usually don't overlap, so this is mostly exact. With
+    /// overlaps, the range of candidates is widened to the first segment
+    /// that could still extend beyond `start`, and callers have to check the
+    /// end addresses.
+    pub fn candidates(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl DoubleEndedIterator<Item = &SegmentIndexEntry> {
+        let entries = &self.entries;
+        let lo = entries.partition_point(|e| e.max_end <= start);
+        let hi = entries.partition_point(|e| e.vm_start < end);
+        entries.get(lo..hi).unwrap_or_default().iter()
+    }
+
+    /// Return the position of the segment containing the given address.
+    ///
+    /// If segments overlap, the one with the highest start address wins.
+    pub fn position_of(&self, addr: usize) -> Option<usize> {
+        let end = addr.checked_add(1)?;
+        self.candidates(addr, end)
+            .rev()
+            .find(|e| e.range().contains(&addr))
+            .map(|e| e.index)
+    }
+}
+
+impl<'d> TryFrom<&'d [u8]> for Core<'d> {
+    type Error = ParseError;
+
+    fn try_from(data: &'d [u8]) -> Result<Self, Self::Error> {
+        Self::parse(data)
+    }
+}
+
+impl fmt::Debug for Core<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Core")
+            .field("segments", &self.segments)
+            .field("process", &self.process)
+            .field("threads", &self.threads)
+            .field("file_map", &self.file_map)
+            .field("signal", &self.signal)
+            .field("custom_notes", &self.custom_notes)
+            .finish()
+    }
+}
+
+/// The metadata of a core file, without its segments.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoreSummary<'d> {
+    pub process: ProcessInfo<'d>,
+    pub threads: Vec<ThreadInfo<'d>>,
+    pub file_map: Vec<FileMapping<'d>>,
+    pub signal: Option<SignalInfo>,
+}
+
+impl<'d> CoreSummary<'d> {
+    fn extract(
+        elf: &Elf<'d>,
+        options: &ParseOptions,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Self, ParseError> {
+        let mut process = extract_process_info(elf, options)?;
+        let threads = extract_thread_infos(elf, options)?;
+        process.current_signal = threads.iter().find_map(|t| t.current_signal);
+
+        Ok(Self {
+            process,
+            threads,
+            file_map: extract_file_map(elf, options, warnings)?,
+            signal: extract_signal_info(elf)?,
+        })
+    }
+}
+
+/// A loadable segment of a core file.
+///
+/// The segment spans the half-open address range `[vm_start, vm_end)`.
+///
+/// Segments are compared, ordered, and hashed by their address range
+/// `(vm_start, vm_end)` only. The other fields, in particular `data`, are
+/// intentionally excluded, so comparisons are cheap and [`Ord`] stays
+/// consistent with [`Eq`]. Two segments spanning the same range are thus
+/// equal even if their contents differ.
+#[derive(Clone)]
 pub struct Segment<'d> {
+    pub id: SegmentId,
     pub vm_start: usize,
     pub vm_end: usize,
+    /// The physical address of the segment, as recorded by the dumper.
+    ///
+    /// The kernel sets this to zero.
+    pub physical_address: usize,
+    pub flags: SegmentFlags,
+    /// The alignment of the segment in memory and in the file. Values of 0
+    /// and 1 both mean that the segment has no alignment requirement.
+    pub align: usize,
     pub data: &'d [u8],
 }
 
+impl PartialEq for Segment<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.range() == other.range()
+    }
+}
+
+impl Eq for Segment<'_> {}
+
+impl Hash for Segment<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.range().hash(state);
+    }
+}
+
+impl Ord for Segment<'_> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (self.vm_start, self.vm_end).cmp(&(other.vm_start, other.vm_end))
+    }
+}
+
+impl PartialOrd for Segment<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'d> Segment<'d> {
+    /// Return whether the given virtual address lies within this segment.
+    pub fn contains(&self, addr: usize) -> bool {
+        self.vm_start <= addr && addr < self.vm_end
+    }
+
+    /// Return the size of the memory spanned by this segment.
+    pub fn len(&self) -> usize {
+        self.vm_end - self.vm_start
+    }
+
+    /// Return whether this segment spans no memory.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the half-open virtual address range spanned by this segment.
+    pub fn range(&self) -> Range<usize> {
+        self.vm_start..self.vm_end
+    }
+
+    /// Return whether the physical address is set and differs from the
+    /// virtual address.
+    ///
+    /// This is unusual for process cores and can indicate a non-standard
+    /// dumper.
+    pub fn has_divergent_physical_address(&self) -> bool {
+        self.physical_address != 0 && self.physical_address != self.vm_start
+    }
+
+    /// Return a reader over the data of this segment.
+    pub fn reader(&self) -> impl Read + 'd {
+        self.data
+    }
+
+    /// Return the CRC-32 checksum of the data of this segment.
+    ///
+    /// Only the bytes present in the core file are covered. Memory beyond
+    /// the file-backed part of the segment, which reads as zeros, does not
+    /// contribute to the checksum.
+    #[cfg(feature = "digest")]
+    pub fn crc32(&self) -> u32 {
+        const CRC32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+        CRC32.checksum(self.data)
+    }
+}
+
 impl fmt::Debug for Segment<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Segment")
+            .field("id", &self.id)
             .field("vm_start", &format_args!("{:#x}", self.vm_start))
             .field("vm_end", &format_args!("{:#x}", self.vm_end))
+            .field(
+                "physical_address",
+                &format_args!("{:#x}", self.physical_address),
+            )
+            .field("flags", &self.flags)
+            .field("align", &format_args!("{:#x}", self.align))
             .field("data", &format_args!("…"))
             .finish()
     }
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// The identity of a segment, i.e. the index of its program header.
+///
+/// This matches the ordinal of the segment's program header as shown by
+/// `readelf -l`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SegmentId(pub usize);
+
+/// The memory permissions of a segment.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SegmentFlags(u32);
+
+impl SegmentFlags {
+    pub const EXECUTE: Self = Self(PF_X);
+    pub const WRITE: Self = Self(PF_W);
+    pub const READ: Self = Self(PF_R);
+
+    /// Create segment flags from the raw `p_flags` value.
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Return the raw `p_flags` value.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Return whether all flags in `other` are set in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_readable(self) -> bool {
+        self.contains(Self::READ)
+    }
+
+    pub fn is_writable(self) -> bool {
+        self.contains(Self::WRITE)
+    }
+
+    pub fn is_executable(self) -> bool {
+        self.contains(Self::EXECUTE)
+    }
+}
+
+impl fmt::Debug for SegmentFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = if self.is_readable() { 'r' } else { '-' };
+        let w = if self.is_writable() { 'w' } else { '-' };
+        let x = if self.is_executable() { 'x' } else { '-' };
+        write!(f, "{r}{w}{x}")
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ProcessInfo<'d> {
     pub state: i8,
     pub state_name: char,
@@ -61,6 +944,7 @@ pub struct ProcessInfo<'d> {
     pub sid: i32,
     pub file_name: &'d [u8],
     pub command: &'d [u8],
+    current_signal: Option<u32>,
 }
 
 impl fmt::Debug for ProcessInfo<'_> {
@@ -77,14 +961,15 @@ impl fmt::Debug for ProcessInfo<'_> {
             .field("ppid", &self.ppid)
             .field("pgrp", &self.pgrp)
             .field("sid", &self.sid)
-            .field("file_name", &String::from_utf8_lossy(self.file_name))
-            .field("command", &String::from_utf8_lossy(self.command))
+            .field("file_name", &self.file_name_str())
+            .field("command", &self.command_str())
+            .field("current_signal", &self.current_signal)
             .finish()
     }
 }
 
-impl<'d> From<&'d elf_prpsinfo> for ProcessInfo<'d> {
-    fn from(prpsinfo: &'d elf_prpsinfo) -> Self {
+impl<'d, BO: byteorder::ByteOrder> From<&'d elf_prpsinfo<BO>> for ProcessInfo<'d> {
+    fn from(prpsinfo: &'d elf_prpsinfo<BO>) -> Self {
         Self {
             state: prpsinfo.pr_state,
             state_name: prpsinfo.pr_sname.into(),
@@ -99,27 +984,288 @@ impl<'d> From<&'d elf_prpsinfo> for ProcessInfo<'d> {
             sid: prpsinfo.pr_sid.to_int(),
             file_name: trim_c_string(&prpsinfo.pr_fname),
             command: trim_c_string(&prpsinfo.pr_psargs),
+            current_signal: None,
         }
     }
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[derive(Debug)]
-pub struct ThreadInfo {
+impl<'d> ProcessInfo<'d> {
+    /// Return the signal that caused the core dump, if any.
+    ///
+    /// This is taken from the `pr_cursig` of the first thread reporting a
+    /// signal. The kernel writes the thread that triggered the dump first,
+    /// and records the same signal for all threads.
+    pub fn current_signal(&self) -> Option<u32> {
+        self.current_signal
+    }
+
+    /// Set the signal returned by [`ProcessInfo::current_signal`].
+    pub fn with_current_signal(mut self, signal: Option<u32>) -> Self {
+        self.current_signal = signal;
+        self
+    }
+
+    /// Return a human-readable description of the process state.
+    ///
+    /// The descriptions follow the ones used in `/proc/[pid]/status`.
+    pub fn state_description(&self) -> &'static str {
+        match self.state_name {
+            'R' => "Running",
+            'S' => "Sleeping",
+            'D' => "Disk sleep",
+            'T' => "Stopped",
+            't' => "Tracing stop",
+            'X' => "Dead",
+            'Z' => "Zombie",
+            'P' => "Parked",
+            'I' => "Idle",
+            _ => "Unknown",
+        }
+    }
+
+    /// Split the command line into its arguments.
+    ///
+    /// The kernel joins the arguments with spaces, so arguments that contain
+    /// spaces themselves cannot be told apart. The command line is also
+    /// truncated to 80 bytes, so trailing arguments of long command lines are
+    /// missing and the last argument may be cut short.
+    pub fn args(&self) -> Vec<&'d [u8]> {
+        // Each argument's terminating NUL is replaced by a space, including
+        // that of the last argument.
+        let command = self.command.strip_suffix(b" ").unwrap_or(self.command);
+        if command.is_empty() {
+            return Vec::new();
+        }
+
+        command.split(|&b| b == b' ').collect()
+    }
+
+    /// Return the executable file name as a string.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`. The string is only
+    /// copied if such replacements are necessary.
+    pub fn file_name_str(&self) -> Cow<'d, str> {
+        String::from_utf8_lossy(self.file_name)
+    }
+
+    /// Return the command line as a string.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`. The string is only
+    /// copied if such replacements are necessary.
+    pub fn command_str(&self) -> Cow<'d, str> {
+        String::from_utf8_lossy(self.command)
+    }
+}
+
+/// The state of a thread at the time of the core dump.
+///
+/// Note that the derived `PartialEq` and `Hash` impls compare the full thread
+/// state, including all registers. Use [`ThreadInfo::id`] to identify the
+/// same thread across snapshots.
+///
+/// The derived `Ord` impl compares fields in declaration order. It allows
+/// storing threads in ordered collections, but the order is not meaningful
+/// beyond the PID. Use [`ThreadInfo::cmp_by_pid`],
+/// [`ThreadInfo::cmp_by_cpu_time`], or [`ThreadInfo::cmp_by_signal`] with
+/// `sort_by` instead.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadInfo<'d> {
     pub pid: i32,
     pub registers: Registers,
+    /// Whether the thread's floating point state is valid. If this is
+    /// `false`, the thread's `NT_PRFPREG` state should not be trusted.
+    pub fp_valid: bool,
+    /// CPU time the thread spent in user mode.
+    pub user_time: Duration,
+    /// CPU time the thread spent in kernel mode.
+    pub system_time: Duration,
+    /// The signal currently being delivered to the thread, if any.
+    pub current_signal: Option<u32>,
+    /// Signals pending delivery to the thread.
+    pub pending_signals: SignalSet,
+    /// Signals blocked by the thread.
+    pub blocked_signals: SignalSet,
+    prstatus: &'d [u8],
+    io_permission_bitmap: Option<&'d [u8]>,
+    debug_registers: Option<[u64; 8]>,
+}
+
+impl fmt::Debug for ThreadInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadInfo")
+            .field("pid", &self.pid)
+            .field("registers", &self.registers)
+            .field("fp_valid", &self.fp_valid)
+            .field("user_time", &self.user_time)
+            .field("system_time", &self.system_time)
+            .field("current_signal", &self.current_signal)
+            .field("pending_signals", &self.pending_signals)
+            .field("blocked_signals", &self.blocked_signals)
+            .field("prstatus", &format_args!("…"))
+            .field(
+                "io_permission_bitmap",
+                &self.io_permission_bitmap.map(|_| format_args!("…")),
+            )
+            .field("debug_registers", &self.debug_registers)
+            .finish()
+    }
+}
+
+/// The identity of a thread, i.e. its kernel thread ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadId(pub i32);
+
+impl<'d> ThreadInfo<'d> {
+    /// Return the identity of this thread.
+    pub fn id(&self) -> ThreadId {
+        ThreadId(self.pid)
+    }
+
+    /// Return the current, pending, and blocked signals of this thread.
+    pub fn signal_state(&self) -> SignalState {
+        SignalState {
+            current: self.current_signal,
+            pending: self.pending_signals,
+            blocked: self.blocked_signals,
+        }
+    }
+
+    /// Return the total CPU time of this thread, in user and kernel mode.
+    pub fn total_cpu_time(&self) -> Duration {
+        self.user_time.saturating_add(self.system_time)
+    }
+
+    /// Compare two threads by PID.
+    pub fn cmp_by_pid(a: &Self, b: &Self) -> cmp::Ordering {
+        a.pid.cmp(&b.pid)
+    }
+
+    /// Compare two threads by their total CPU time, in ascending order.
+    ///
+    /// Threads with the same CPU time are ordered by PID.
+    pub fn cmp_by_cpu_time(a: &Self, b: &Self) -> cmp::Ordering {
+        a.total_cpu_time()
+            .cmp(&b.total_cpu_time())
+            .then_with(|| Self::cmp_by_pid(a, b))
+    }
+
+    /// Compare two threads by the signal currently being delivered to them.
+    ///
+    /// Threads without a current signal come first. Threads with the same
+    /// signal are ordered by PID.
+    pub fn cmp_by_signal(a: &Self, b: &Self) -> cmp::Ordering {
+        a.current_signal
+            .cmp(&b.current_signal)
+            .then_with(|| Self::cmp_by_pid(a, b))
+    }
+
+    /// Return the raw `NT_PRSTATUS` note descriptor of this thread.
+    ///
+    /// The bytes have the layout of the kernel's `struct elf_prstatus`, in
+    /// the byte order of the core file. This allows reading fields that are
+    /// not exposed by [`ThreadInfo`].
+    pub fn raw_prstatus(&self) -> &'d [u8] {
+        self.prstatus
+    }
+
+    /// Return the I/O permission bitmap of this thread.
+    ///
+    /// The kernel only writes the `NT_386_IOPERM` note for threads that were
+    /// granted access to I/O ports through `ioperm(2)`. A set bit denies
+    /// access to the corresponding port.
+    pub fn io_permission_bitmap(&self) -> Option<&'d [u8]> {
+        self.io_permission_bitmap
+    }
+
+    /// Return the debug registers DR0 to DR7 of this thread.
+    ///
+    /// Returns `None` if the core has no debug register note for this
+    /// thread, or if [`ParseOptions::debug_registers_note_type`] is not set.
+    pub fn debug_registers(&self) -> Option<[u64; 8]> {
+        self.debug_registers
+    }
+
+    /// Set the descriptor returned by [`ThreadInfo::raw_prstatus`].
+    pub fn with_raw_prstatus(mut self, prstatus: &'d [u8]) -> Self {
+        self.prstatus = prstatus;
+        self
+    }
+
+    /// Set the bitmap returned by [`ThreadInfo::io_permission_bitmap`].
+    pub fn with_io_permission_bitmap(mut self, bitmap: Option<&'d [u8]>) -> Self {
+        self.io_permission_bitmap = bitmap;
+        self
+    }
+
+    /// Set the registers returned by [`ThreadInfo::debug_registers`].
+    pub fn with_debug_registers(mut self, registers: Option<[u64; 8]>) -> Self {
+        self.debug_registers = registers;
+        self
+    }
+
+    /// Return the general purpose registers in the order the kernel stores
+    /// them in `elf_gregset_t`.
+    pub fn raw_gregset(&self) -> [u64; 27] {
+        self.registers.gregset()
+    }
+
+    /// Return whether this thread looks like a kernel thread, whose register
+    /// state is meaningless.
+    ///
+    /// This is a best-effort heuristic. A thread is considered a kernel
+    /// thread if its instruction pointer is zero, if its stack pointer does
+    /// not point into any segment of the core, or if the process command is
+    /// enclosed in brackets, as `ps` shows kernel threads. Note that the stack
+    /// check also matches all threads of cores that contain no segments.
+    pub fn is_kernel_thread(&self, core: &Core) -> bool {
+        let command = core.process.command.strip_suffix(b" ");
+        let command = command.unwrap_or(core.process.command);
+        let bracketed = command.starts_with(b"[") && command.ends_with(b"]");
+
+        self.registers.instruction_pointer() == 0
+            || core
+                .segment_at(self.registers.stack_pointer() as usize)
+                .is_none()
+            || bracketed
+    }
 }
 
-impl From<&elf_prstatus> for ThreadInfo {
-    fn from(prstatus: &elf_prstatus) -> Self {
+impl<'d> ThreadInfo<'d> {
+    /// Create a thread from its `NT_PRSTATUS` note descriptor `raw`, parsed
+    /// as `prstatus`.
+    fn from_prstatus<BO: byteorder::ByteOrder>(prstatus: &elf_prstatus<BO>, raw: &'d [u8]) -> Self {
         Self {
             pid: prstatus.common.pr_pid.to_int(),
             registers: (&prstatus.pr_reg).into(),
+            fp_valid: prstatus.pr_fpvalid.to_int() != 0,
+            user_time: timeval_to_duration(&prstatus.common.pr_utime),
+            system_time: timeval_to_duration(&prstatus.common.pr_stime),
+            current_signal: match prstatus.common.pr_cursig.to_int() {
+                0 => None,
+                sig => Some(sig as u32),
+            },
+            pending_signals: SignalSet::from_bits(prstatus.common.pr_sigpend.to_int()),
+            blocked_signals: SignalSet::from_bits(prstatus.common.pr_sighold.to_int()),
+            prstatus: raw,
+            io_permission_bitmap: None,
+            debug_registers: None,
         }
     }
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Convert a kernel timeval into a `Duration`, clamping negative values to
+/// zero.
+fn timeval_to_duration<BO: byteorder::ByteOrder>(tv: &__kernel_old_timeval<BO>) -> Duration {
+    let secs = tv.tv_sec.to_int().max(0) as u64;
+    let micros = tv.tv_usec.to_int().max(0) as u64;
+    Duration::from_secs(secs).saturating_add(Duration::from_micros(micros))
+}
+
+/// The general-purpose registers of a thread.
+///
+/// The [`Default`] value has all registers zeroed, which together with
+/// struct update syntax is convenient for constructing registers in tests.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Registers {
     pub rax: u64,
     pub rbx: u64,
@@ -129,6 +1275,7 @@ pub struct Registers {
     pub rsp: u64,
     pub rsi: u64,
     pub rdi: u64,
+    pub orig_rax: u64,
     pub r8: u64,
     pub r9: u64,
     pub r10: u64,
@@ -149,6 +1296,98 @@ pub struct Registers {
     pub gs_base: u64,
 }
 
+impl Registers {
+    /// Return the instruction pointer (`rip` on x86-64).
+    pub fn instruction_pointer(&self) -> u64 {
+        self.rip
+    }
+
+    /// Return the stack pointer (`rsp` on x86-64).
+    pub fn stack_pointer(&self) -> u64 {
+        self.rsp
+    }
+
+    /// Return the frame pointer (`rbp` on x86-64).
+    pub fn frame_pointer(&self) -> u64 {
+        self.rbp
+    }
+
+    /// Return the value of the register with the given DWARF register number,
+    /// as defined by the System V x86-64 psABI.
+    ///
+    /// Returns `None` for registers that are not part of the general purpose
+    /// register set, like the SSE or x87 registers.
+    pub fn dwarf(&self, reg: u16) -> Option<u64> {
+        let value = match reg {
+            0 => self.rax,
+            1 => self.rdx,
+            2 => self.rcx,
+            3 => self.rbx,
+            4 => self.rsi,
+            5 => self.rdi,
+            6 => self.rbp,
+            7 => self.rsp,
+            8 => self.r8,
+            9 => self.r9,
+            10 => self.r10,
+            11 => self.r11,
+            12 => self.r12,
+            13 => self.r13,
+            14 => self.r14,
+            15 => self.r15,
+            16 => self.rip,
+            49 => self.rflags,
+            50 => self.es,
+            51 => self.cs,
+            52 => self.ss,
+            53 => self.ds,
+            54 => self.fs,
+            55 => self.gs,
+            58 => self.fs_base,
+            59 => self.gs_base,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Return the name of the register with the given DWARF register number.
+    ///
+    /// This covers the same registers as [`Registers::dwarf`]. DWARF number
+    /// 16 is the return address column, which holds `rip`.
+    pub fn dwarf_reg_name(reg: u16) -> Option<&'static str> {
+        let name = match reg {
+            0 => "rax",
+            1 => "rdx",
+            2 => "rcx",
+            3 => "rbx",
+            4 => "rsi",
+            5 => "rdi",
+            6 => "rbp",
+            7 => "rsp",
+            8 => "r8",
+            9 => "r9",
+            10 => "r10",
+            11 => "r11",
+            12 => "r12",
+            13 => "r13",
+            14 => "r14",
+            15 => "r15",
+            16 => "rip",
+            49 => "rflags",
+            50 => "es",
+            51 => "cs",
+            52 => "ss",
+            53 => "ds",
+            54 => "fs",
+            55 => "gs",
+            58 => "fs_base",
+            59 => "gs_base",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
 impl fmt::Debug for Registers {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Registers")
@@ -160,6 +1399,7 @@ impl fmt::Debug for Registers {
             .field("rsp", &format_args!("{:#018x}", self.rsp))
             .field("rsi", &format_args!("{:#018x}", self.rsi))
             .field("rdi", &format_args!("{:#018x}", self.rdi))
+            .field("orig_rax", &format_args!("{:#018x}", self.orig_rax))
             .field("r8", &format_args!("{:#018x}", self.r8))
             .field("r9", &format_args!("{:#018x}", self.r9))
             .field("r10", &format_args!("{:#018x}", self.r10))
@@ -182,8 +1422,8 @@ impl fmt::Debug for Registers {
     }
 }
 
-impl From<&elf_gregset_t> for Registers {
-    fn from(gregset: &elf_gregset_t) -> Self {
+impl<BO: byteorder::ByteOrder> From<&elf_gregset_t<BO>> for Registers {
+    fn from(gregset: &elf_gregset_t<BO>) -> Self {
         Self {
             rax: gregset.ax.to_int(),
             rbx: gregset.bx.to_int(),
@@ -193,6 +1433,7 @@ impl From<&elf_gregset_t> for Registers {
             rsp: gregset.sp.to_int(),
             rsi: gregset.si.to_int(),
             rdi: gregset.di.to_int(),
+            orig_rax: gregset.orig_ax.to_int(),
             r8: gregset.r8.to_int(),
             r9: gregset.r9.to_int(),
             r10: gregset.r10.to_int(),
@@ -215,12 +1456,84 @@ impl From<&elf_gregset_t> for Registers {
     }
 }
 
+/// A file-backed memory mapping, as recorded in the `NT_FILE` note.
+///
+/// The mapping spans the half-open address range `[vm_start, vm_end)`.
+///
+/// If the note was truncated, `file_path` is empty for mappings whose path
+/// could not be recovered.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FileMapping<'d> {
     pub vm_start: usize,
     pub vm_end: usize,
     pub file_offset: u64,
     pub file_path: &'d [u8],
+    /// Permissions of the `PT_LOAD` segment covering the start of the
+    /// mapping, or `None` if no segment covers it.
+    pub flags: Option<SegmentFlags>,
+}
+
+impl<'d> FileMapping<'d> {
+    /// Return the path of the mapped file as a string.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`. The string is only
+    /// copied if such replacements are necessary.
+    pub fn file_path_str(&self) -> Cow<'d, str> {
+        String::from_utf8_lossy(self.file_path)
+    }
+
+    /// Return whether the given virtual address lies within this mapping.
+    pub fn contains(&self, addr: usize) -> bool {
+        self.vm_start <= addr && addr < self.vm_end
+    }
+
+    /// Format this mapping as a line of `/proc/<pid>/maps`.
+    ///
+    /// The permissions are taken from [`FileMapping::flags`]. Without them,
+    /// `----` is printed. Otherwise, mappings are reported as private, and
+    /// the device and inode fields are always zero, as neither is known.
+    pub fn format_maps_line(&self) -> String {
+        let perms: String = match self.flags {
+            Some(flags) => [
+                if flags.is_readable() { 'r' } else { '-' },
+                if flags.is_writable() { 'w' } else { '-' },
+                if flags.is_executable() { 'x' } else { '-' },
+                'p',
+            ]
+            .into_iter()
+            .collect(),
+            None => "----".to_string(),
+        };
+        let line = format!(
+            "{:08x}-{:08x} {perms} {:08x} 00:00 0",
+            self.vm_start, self.vm_end, self.file_offset,
+        );
+        // The kernel pads the path to start at column 73 on 64-bit systems.
+        format!("{line:<72} {}", self.file_path_str())
+    }
+
+    /// Return whether the contents of this mapping are fully present in the
+    /// given core.
+    ///
+    /// A mapping may be listed in the file map even though the kernel did
+    /// not dump its memory, e.g. for unmodified file-backed code.
+    pub fn is_present(&self, core: &Core) -> bool {
+        let mut cur = self.vm_start;
+        while cur < self.vm_end {
+            let Some(segment) = core.segment_at(cur) else {
+                return false;
+            };
+
+            // Segments may contain fewer bytes than they span.
+            let present_end = segment.vm_start + segment.data.len();
+            if cur >= present_end {
+                return false;
+            }
+            cur = present_end;
+        }
+
+        true
+    }
 }
 
 impl fmt::Debug for FileMapping<'_> {
@@ -229,28 +1542,74 @@ impl fmt::Debug for FileMapping<'_> {
             .field("vm_start", &format_args!("{:#x}", self.vm_start))
             .field("vm_end", &format_args!("{:#x}", self.vm_end))
             .field("file_offset", &format_args!("{:#x}", self.file_offset))
-            .field("file_path", &String::from_utf8_lossy(self.file_path))
+            .field("file_path", &self.file_path_str())
+            .field("flags", &self.flags)
             .finish()
     }
 }
 
-fn extract_segments<'d>(elf: &Elf<'d>) -> Result<Vec<Segment<'d>>, ParseError> {
+fn extract_segments<'d>(
+    elf: &Elf<'d>,
+    options: &ParseOptions,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Vec<Segment<'d>>, ParseError> {
     let mut segments = Vec::new();
     for ph in elf.iter_program_headers(PT_LOAD) {
+        let id = SegmentId(ph.index);
         if ph.memory_size != ph.file_size {
-            Err(format!(
-                "segment file size ({:#x}) differs from memory size ({:#x})",
-                ph.file_size, ph.memory_size
-            ))?;
+            if !options.allow_size_mismatch {
+                return Err(ParseError::SegmentSizeMismatch {
+                    segment: id,
+                    file_size: ph.file_size,
+                    memory_size: ph.memory_size,
+                });
+            }
+            warnings.push(ParseWarning::SegmentSizeMismatch {
+                segment: id,
+                file_size: ph.file_size,
+                memory_size: ph.memory_size,
+            });
         }
 
         let vm_start = ph.memory_address;
-        let vm_end = vm_start + ph.memory_size;
-        let data = elf.read_segment(ph)?;
+        if ph.physical_address != 0 && ph.physical_address != vm_start {
+            warnings.push(ParseWarning::DivergentPhysicalAddress {
+                segment: id,
+                virtual_address: vm_start,
+                physical_address: ph.physical_address,
+            });
+        }
+
+        let vm_end =
+            vm_start
+                .checked_add(ph.memory_size)
+                .ok_or(ParseError::SegmentRangeOverflow {
+                    start: vm_start,
+                    size: ph.memory_size,
+                })?;
+        let data = if options.tolerate_truncation {
+            elf.read_segment_truncated(ph)
+        } else {
+            elf.read_segment(ph)?
+        };
+        if data.len() < ph.file_size {
+            warnings.push(ParseWarning::TruncatedSegment {
+                segment: id,
+                file_size: ph.file_size,
+                present: data.len(),
+            });
+        }
+        // With a size mismatch, the file may contain more data than the
+        // segment spans in memory.
+        let data = &data[..data.len().min(ph.memory_size)];
 
         segments.push(Segment {
+            id,
             vm_start,
             vm_end,
+            physical_address: ph.physical_address,
+            flags: SegmentFlags::from_bits(ph.flags),
+            align: ph.align,
             data,
         });
     }
@@ -258,51 +1617,189 @@ fn extract_segments<'d>(elf: &Elf<'d>) -> Result<Vec<Segment<'d>>, ParseError> {
     Ok(segments)
 }
 
-fn extract_process_info<'d>(elf: &Elf<'d>) -> Result<ProcessInfo<'d>, ParseError> {
-    let data = elf
-        .get_note(b"CORE", NT_PRPSINFO)
-        .ok_or_else(|| "missing note: CORE/NT_PRPSINFO".to_string())?;
+fn extract_process_info<'d>(
+    elf: &Elf<'d>,
+    options: &ParseOptions,
+) -> Result<ProcessInfo<'d>, ParseError> {
+    let note = match elf.get_note_entry(CORE_OWNERS, NT_PRPSINFO) {
+        Some(note) => note,
+        None if options.allow_missing_notes => return Ok(ProcessInfo::default()),
+        None => {
+            return Err(ParseError::MissingNote {
+                name: "CORE/NT_PRPSINFO",
+            })
+        }
+    };
 
-    elf_prpsinfo::parse(data).map(Into::into)
+    let info = with_byte_order!(elf.byte_order(), BO => {
+        elf_prpsinfo::<BO>::parse(note.desc).map(Into::into)
+    });
+    info.map_err(|e| ParseError::InvalidNote {
+        note: "NT_PRPSINFO note",
+        offset: note.offset,
+        reason: e.into_message(),
+    })
 }
 
-fn extract_thread_infos(elf: &Elf<'_>) -> Result<Vec<ThreadInfo>, ParseError> {
-    elf.iter_notes(b"CORE", NT_PRSTATUS)
-        .map(|data| elf_prstatus::parse(data).map(Into::into))
-        .collect()
+fn extract_thread_infos<'d>(
+    elf: &Elf<'d>,
+    options: &ParseOptions,
+) -> Result<Vec<ThreadInfo<'d>>, ParseError> {
+    // The kernel writes the notes of each thread in a group that starts with
+    // its `NT_PRSTATUS` note.
+    let notes = elf.notes();
+    let starts: Vec<_> = notes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| CORE_OWNERS.contains(&n.name) && n.type_ == NT_PRSTATUS)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut threads = Vec::with_capacity(starts.len());
+    for (i, &start) in starts.iter().enumerate() {
+        let note = &notes[start];
+        let end = starts.get(i + 1).copied().unwrap_or(notes.len());
+        let group = &notes[start + 1..end];
+        let wrap_error = |reason| ParseError::InvalidThreadNote {
+            thread: i,
+            offset: note.offset,
+            reason,
+        };
+
+        let mut thread = with_byte_order!(elf.byte_order(), BO => {
+            elf_prstatus::<BO>::parse(note.desc)
+                .map(|prstatus| ThreadInfo::from_prstatus(prstatus, note.desc))
+        })
+        .map_err(|e| wrap_error(e.into_message()))?;
+
+        thread.io_permission_bitmap = group
+            .iter()
+            .find(|n| (n.name, n.type_) == (LINUX, NT_386_IOPERM))
+            .map(|n| n.desc);
+        if let Some(type_) = options.debug_registers_note_type {
+            let note = group
+                .iter()
+                .find(|n| CORE_OWNERS.contains(&n.name) && n.type_ == type_);
+            if let Some(note) = note {
+                let regs = parse_debug_registers(note.desc, elf.byte_order())
+                    .map_err(|e| wrap_error(format!("debug register note: {e}")))?;
+                thread.debug_registers = Some(regs);
+            }
+        }
+        threads.push(thread);
+    }
+    Ok(threads)
 }
 
-fn extract_file_map<'d>(elf: &Elf<'d>) -> Result<Vec<FileMapping<'d>>, ParseError> {
-    let wrap_error = |e| format!("NT_FILE note: {e}");
+/// Parse the descriptor of a debug register note, consisting of the values
+/// of DR0 to DR7.
+fn parse_debug_registers(mut desc: &[u8], order: ByteOrder) -> read::Result<[u64; 8]> {
+    let mut regs = [0; 8];
+    for reg in &mut regs {
+        *reg = desc.read_u64_with(order)?;
+    }
+    Ok(regs)
+}
 
-    let mut data = elf
-        .get_note(b"CORE", NT_FILE)
-        .ok_or_else(|| "missing note: CORE/NT_FILE".to_string())?;
+fn extract_file_map<'d>(
+    elf: &Elf<'d>,
+    options: &ParseOptions,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Vec<FileMapping<'d>>, ParseError> {
+    let note = match elf.get_note_entry(CORE_OWNERS, NT_FILE) {
+        Some(note) => note,
+        None if options.allow_missing_notes => return Ok(Vec::new()),
+        None => {
+            return Err(ParseError::MissingNote {
+                name: "CORE/NT_FILE",
+            })
+        }
+    };
 
-    let count = data.read_u64().map_err(wrap_error)?;
-    let page_size = data.read_u64().map_err(wrap_error)?;
+    let wrap_error = |reason: &str| ParseError::InvalidNote {
+        note: "NT_FILE note",
+        offset: note.offset,
+        reason: reason.to_string(),
+    };
+    let order = elf.byte_order();
+    let mut data = note.desc;
 
-    let mut mappings = Vec::with_capacity(count as usize);
+    let count = data.read_u64_with(order).map_err(wrap_error)?;
+    let page_size = data.read_u64_with(order).map_err(wrap_error)?;
+
+    // Each mapping entry takes 24 bytes, so the count can't exceed what's
+    // left of the note. Check this upfront to reject bogus counts early,
+    // and grow the vector incrementally rather than trusting the count.
+    const ENTRY_SIZE: u64 = 24;
+    if count > data.len() as u64 / ENTRY_SIZE {
+        return Err(wrap_error("mapping count exceeds note size"));
+    }
+
+    // The `NT_FILE` note doesn't record permissions, so we take them from the
+    // `PT_LOAD` segments, sorted by start address for lookup.
+    let mut loads: Vec<_> = elf
+        .iter_program_headers(PT_LOAD)
+        .map(|ph| {
+            let end = ph.memory_address.saturating_add(ph.memory_size);
+            (ph.memory_address, end, SegmentFlags::from_bits(ph.flags))
+        })
+        .collect();
+    loads.sort_by_key(|&(start, ..)| start);
+    let flags_at = |addr: usize| {
+        let pos = loads.partition_point(|&(start, ..)| start <= addr);
+        let &(_, end, flags) = loads[..pos].last()?;
+        (addr < end).then_some(flags)
+    };
+
+    let mut mappings = Vec::new();
     for _ in 0..count {
-        let vm_start = data.read_u64().map_err(wrap_error)?;
-        let vm_end = data.read_u64().map_err(wrap_error)?;
-        let page_idx = data.read_u64().map_err(wrap_error)?;
+        let vm_start = data.read_u64_with(order).map_err(wrap_error)?;
+        let vm_end = data.read_u64_with(order).map_err(wrap_error)?;
+        let page_idx = data.read_u64_with(order).map_err(wrap_error)?;
+        let file_offset = page_idx
+            .checked_mul(page_size)
+            .ok_or_else(|| wrap_error("file offset overflows"))?;
+        if vm_end < vm_start {
+            return Err(wrap_error("mapping ends before it starts"));
+        }
 
         mappings.push(FileMapping {
             vm_start: vm_start as usize,
             vm_end: vm_end as usize,
-            file_offset: page_idx * page_size,
+            file_offset,
             file_path: &[],
+            flags: flags_at(vm_start as usize),
         });
     }
 
+    // Truncated notes can contain fewer paths than mappings. Rather than
+    // failing the whole parse, we leave the paths of the excess mappings empty.
     let mut paths = data.split(|c| *c == b'\0');
+    let mut missing = 0;
     for map in &mut mappings {
-        let path = paths
-            .next()
-            .ok_or_else(|| "NT_FILE note contains too few paths".to_string())?;
-        map.file_path = path;
+        match paths.next() {
+            Some(path) => map.file_path = path,
+            None => missing += 1,
+        }
+    }
+    if missing > 0 {
+        warnings.push(ParseWarning::MissingNtFilePaths { count: missing });
     }
 
     Ok(mappings)
 }
+
+fn extract_signal_info(elf: &Elf<'_>) -> Result<Option<SignalInfo>, ParseError> {
+    let Some(note) = elf.get_note_entry(CORE_OWNERS, NT_SIGINFO) else {
+        return Ok(None);
+    };
+
+    let info = with_byte_order!(elf.byte_order(), BO => {
+        siginfo_t::<BO>::parse(note.desc).map(|siginfo| Some(siginfo.into()))
+    });
+    info.map_err(|e| ParseError::InvalidNote {
+        note: "NT_SIGINFO note",
+        offset: note.offset,
+        reason: e.into_message(),
+    })
+}
@@ -1,9 +1,10 @@
 #![allow(non_camel_case_types)]
 
-use std::fmt::{Debug, Display};
+use std::fmt::Debug;
 use std::mem;
 
-use structview::{i16_le, i32_le, i64_le, u16_le, u32_le, u64_le, View};
+use byteorder::ByteOrder;
+use structview::{View, I16, I32, I64, U16, U32, U64};
 
 use crate::error::ParseError;
 
@@ -14,32 +15,46 @@ pub(crate) trait CType: View {
     const SIZE: usize = mem::size_of::<Self>();
 
     fn parse(data: &[u8]) -> Result<&Self, ParseError> {
-        let obj = Self::view(data).map_err(Self::wrap_error)?;
-        obj.verify().map_err(Self::wrap_error)?;
+        let obj = Self::view(data).map_err(|_| Self::truncated(Self::SIZE, data.len()))?;
+        obj.verify().map_err(Self::invalid)?;
 
         Ok(obj)
     }
 
     fn parse_many(data: &[u8]) -> Result<&[Self], ParseError> {
-        let objs = Self::view_slice(data).map_err(Self::wrap_error)?;
+        let objs = Self::view_slice(data)
+            .map_err(|_| Self::truncated(data.len().next_multiple_of(Self::SIZE), data.len()))?;
         for obj in objs {
-            obj.verify().map_err(Self::wrap_error)?;
+            obj.verify().map_err(Self::invalid)?;
         }
 
         Ok(objs)
     }
 
     fn parse_n(data: &[u8], count: usize) -> Result<&[Self], ParseError> {
-        let size = Self::SIZE * count;
+        // Check the count against the available data before slicing, so a
+        // bogus count can't make us read (or allocate for) too many objects.
+        let size = Self::SIZE.saturating_mul(count);
         let data = data
             .get(..size)
-            .ok_or_else(|| Self::wrap_error("not enough data"))?;
+            .ok_or_else(|| Self::truncated(size, data.len()))?;
 
         Self::parse_many(data)
     }
 
-    fn wrap_error<E: Display>(error: E) -> String {
-        format!("{}: {}", Self::NAME, error)
+    fn truncated(size: usize, available: usize) -> ParseError {
+        ParseError::TruncatedHeader {
+            name: Self::NAME,
+            size,
+            available,
+        }
+    }
+
+    fn invalid(reason: String) -> ParseError {
+        ParseError::InvalidHeader {
+            name: Self::NAME,
+            reason,
+        }
     }
 
     fn verify(&self) -> Result<(), String> {
@@ -61,42 +76,51 @@ where
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct Elf64_Ehdr {
+pub(crate) struct Elf64_Ehdr<BO: ByteOrder> {
     /// ELF "magic number".
     pub e_ident: [u8; 16],
-    pub e_type: u16_le,
-    pub e_machine: u16_le,
-    pub e_version: u32_le,
+    pub e_type: U16<BO>,
+    pub e_machine: U16<BO>,
+    pub e_version: U32<BO>,
     /// Entry point virtual address.
-    pub e_entry: u64_le,
+    pub e_entry: U64<BO>,
     /// Program header table file offset.
-    pub e_phoff: u64_le,
+    pub e_phoff: U64<BO>,
     /// Section header table file offset.
-    pub e_shoff: u64_le,
-    pub e_flags: u32_le,
-    pub e_ehsize: u16_le,
-    pub e_phentsize: u16_le,
-    pub e_phnum: u16_le,
-    pub e_shentsize: u16_le,
-    pub e_shnum: u16_le,
-    pub e_shstrndx: u16_le,
+    pub e_shoff: U64<BO>,
+    pub e_flags: U32<BO>,
+    pub e_ehsize: U16<BO>,
+    pub e_phentsize: U16<BO>,
+    pub e_phnum: U16<BO>,
+    pub e_shentsize: U16<BO>,
+    pub e_shnum: U16<BO>,
+    pub e_shstrndx: U16<BO>,
 }
 
-impl CType for Elf64_Ehdr {
+impl<BO: ByteOrder> CType for Elf64_Ehdr<BO> {
     const NAME: &'static str = "Elf64_Ehdr";
 
     fn verify(&self) -> Result<(), String> {
         expect("e_ident.magic", &self.e_ident[..4], b"\x7fELF")?;
         expect("e_ident.class", self.e_ident[4], ELFCLASS64)?;
-        expect("e_ident.data", self.e_ident[5], ELFDATA2LSB)?;
+        let data = self.e_ident[5];
+        if data != ELFDATA2LSB && data != ELFDATA2MSB {
+            return Err(format!("invalid e_ident.data value: {data:?}"));
+        }
         expect("e_ident.version", self.e_ident[6], EV_CURRENT)?;
-        expect("e_ident.osabi", self.e_ident[7], ELFOSABI_SYSV)?;
+        let osabi = self.e_ident[7];
+        if osabi != ELFOSABI_SYSV && osabi != ELFOSABI_LINUX {
+            return Err(format!("invalid e_ident.osabi value: {osabi:?}"));
+        }
         expect("e_type", self.e_type.to_int(), ET_CORE)?;
         expect("e_machine", self.e_machine.to_int(), EM_X86_64)?;
         expect("e_version", self.e_version.to_int(), EV_CURRENT.into())?;
         expect("e_ehsize", self.e_ehsize.to_int(), 64)?;
         expect("e_phentsize", self.e_phentsize.to_int(), 56)?;
-        expect("e_shentsize", self.e_shentsize.to_int(), 64)?;
+        // Cores without section headers commonly leave `e_shentsize` zero.
+        if self.e_shnum.to_int() != 0 || self.e_shentsize.to_int() != 0 {
+            expect("e_shentsize", self.e_shentsize.to_int(), 64)?;
+        }
 
         Ok(())
     }
@@ -104,180 +128,214 @@ impl CType for Elf64_Ehdr {
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct Elf64_Phdr {
-    pub p_type: u32_le,
-    pub p_flags: u32_le,
+pub(crate) struct Elf64_Phdr<BO: ByteOrder> {
+    pub p_type: U32<BO>,
+    pub p_flags: U32<BO>,
     /// Segment file offset.
-    pub p_offset: u64_le,
+    pub p_offset: U64<BO>,
     /// Segment virtual address.
-    pub p_vaddr: u64_le,
+    pub p_vaddr: U64<BO>,
     /// Segment physical address.
-    pub p_paddr: u64_le,
+    pub p_paddr: U64<BO>,
     /// Segment size in file.
-    pub p_filesz: u64_le,
+    pub p_filesz: U64<BO>,
     /// Segment size in memory.
-    pub p_memsz: u64_le,
+    pub p_memsz: U64<BO>,
     /// Segment alignment, file & memory.
-    pub p_align: u64_le,
+    pub p_align: U64<BO>,
 }
 
-impl CType for Elf64_Phdr {
+impl<BO: ByteOrder> CType for Elf64_Phdr<BO> {
     const NAME: &'static str = "Elf64_Phdr";
+}
 
-    fn verify(&self) -> Result<(), String> {
-        let p_vaddr = self.p_vaddr.to_int();
-        let p_paddr = self.p_paddr.to_int();
-
-        if p_vaddr % self.p_align.to_int() != 0 {
-            Err(format!("unaligned p_vaddr value: {p_vaddr:#x}"))
-        } else if p_paddr % self.p_align.to_int() != 0 {
-            Err(format!("unaligned p_paddr value: {p_paddr:#x}"))
-        } else {
-            Ok(())
-        }
-    }
+#[derive(Clone, Copy, Debug, View)]
+#[repr(C)]
+pub(crate) struct Elf64_Shdr<BO: ByteOrder> {
+    /// Section name (string table index).
+    pub sh_name: U32<BO>,
+    pub sh_type: U32<BO>,
+    pub sh_flags: U64<BO>,
+    /// Section virtual address at execution.
+    pub sh_addr: U64<BO>,
+    /// Section file offset.
+    pub sh_offset: U64<BO>,
+    /// Section size in bytes.
+    pub sh_size: U64<BO>,
+    /// Link to another section.
+    pub sh_link: U32<BO>,
+    /// Additional section information.
+    pub sh_info: U32<BO>,
+    /// Section alignment.
+    pub sh_addralign: U64<BO>,
+    /// Entry size if section holds table.
+    pub sh_entsize: U64<BO>,
+}
+
+impl<BO: ByteOrder> CType for Elf64_Shdr<BO> {
+    const NAME: &'static str = "Elf64_Shdr";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct Elf64_Nhdr {
-    pub n_namesz: u32_le,
-    pub n_descsz: u32_le,
-    pub n_type: u32_le,
+pub(crate) struct Elf64_Nhdr<BO: ByteOrder> {
+    pub n_namesz: U32<BO>,
+    pub n_descsz: U32<BO>,
+    pub n_type: U32<BO>,
 }
 
-impl CType for Elf64_Nhdr {
+impl<BO: ByteOrder> CType for Elf64_Nhdr<BO> {
     const NAME: &'static str = "Elf64_Nhdr";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct elf_prpsinfo {
+pub(crate) struct elf_prpsinfo<BO: ByteOrder> {
     pub pr_state: i8,
     pub pr_sname: u8,
     pub pr_zomb: i8,
     pub pr_nice: i8,
     _pad1: [u8; 4],
-    pub pr_flag: u64_le,
-    pub pr_uid: i32_le,
-    pub pr_gid: i32_le,
-    pub pr_pid: i32_le,
-    pub pr_ppid: i32_le,
-    pub pr_pgrp: i32_le,
-    pub pr_sid: i32_le,
+    pub pr_flag: U64<BO>,
+    pub pr_uid: I32<BO>,
+    pub pr_gid: I32<BO>,
+    pub pr_pid: I32<BO>,
+    pub pr_ppid: I32<BO>,
+    pub pr_pgrp: I32<BO>,
+    pub pr_sid: I32<BO>,
     pub pr_fname: [u8; 16],
     pub pr_psargs: [u8; 80],
 }
 
-impl CType for elf_prpsinfo {
+impl<BO: ByteOrder> CType for elf_prpsinfo<BO> {
     const NAME: &'static str = "elf_prpsinfo";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct elf_prstatus {
-    pub common: elf_prstatus_common,
-    pub pr_reg: elf_gregset_t,
-    pub pr_fpvalid: i32_le,
+pub(crate) struct elf_prstatus<BO: ByteOrder> {
+    pub common: elf_prstatus_common<BO>,
+    pub pr_reg: elf_gregset_t<BO>,
+    pub pr_fpvalid: I32<BO>,
 }
 
-impl CType for elf_prstatus {
+impl<BO: ByteOrder> CType for elf_prstatus<BO> {
     const NAME: &'static str = "elf_prstatus";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct elf_prstatus_common {
-    pub pr_info: elf_siginfo,
-    pub pr_cursig: i16_le,
+pub(crate) struct elf_prstatus_common<BO: ByteOrder> {
+    pub pr_info: elf_siginfo<BO>,
+    pub pr_cursig: I16<BO>,
     _pad1: [u8; 2],
-    pub pr_sigpend: u64_le,
-    pub pr_sighold: u64_le,
-    pub pr_pid: i32_le,
-    pub pr_ppid: i32_le,
-    pub pr_pgrp: i32_le,
-    pub pr_sid: i32_le,
-    pub pr_utime: __kernel_old_timeval,
-    pub pr_stime: __kernel_old_timeval,
-    pub pr_cutime: __kernel_old_timeval,
-    pub pr_cstime: __kernel_old_timeval,
+    pub pr_sigpend: U64<BO>,
+    pub pr_sighold: U64<BO>,
+    pub pr_pid: I32<BO>,
+    pub pr_ppid: I32<BO>,
+    pub pr_pgrp: I32<BO>,
+    pub pr_sid: I32<BO>,
+    pub pr_utime: __kernel_old_timeval<BO>,
+    pub pr_stime: __kernel_old_timeval<BO>,
+    pub pr_cutime: __kernel_old_timeval<BO>,
+    pub pr_cstime: __kernel_old_timeval<BO>,
 }
 
-impl CType for elf_prstatus_common {
+impl<BO: ByteOrder> CType for elf_prstatus_common<BO> {
     const NAME: &'static str = "elf_prstatus_common";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct elf_siginfo {
-    pub si_signo: i32_le,
-    pub si_code: i32_le,
-    pub si_errno: i32_le,
+pub(crate) struct elf_siginfo<BO: ByteOrder> {
+    pub si_signo: I32<BO>,
+    pub si_code: I32<BO>,
+    pub si_errno: I32<BO>,
 }
 
-impl CType for elf_siginfo {
+impl<BO: ByteOrder> CType for elf_siginfo<BO> {
     const NAME: &'static str = "elf_siginfo";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct elf_gregset_t {
-    pub r15: u64_le,
-    pub r14: u64_le,
-    pub r13: u64_le,
-    pub r12: u64_le,
-    pub bp: u64_le,
-    pub bx: u64_le,
-    pub r11: u64_le,
-    pub r10: u64_le,
-    pub r9: u64_le,
-    pub r8: u64_le,
-    pub ax: u64_le,
-    pub cx: u64_le,
-    pub dx: u64_le,
-    pub si: u64_le,
-    pub di: u64_le,
-    pub orig_ax: u64_le,
-    pub ip: u64_le,
-    pub cs: u64_le,
-    pub flags: u64_le,
-    pub sp: u64_le,
-    pub ss: u64_le,
-    pub fs_base: u64_le,
-    pub gs_base: u64_le,
-    pub ds: u64_le,
-    pub es: u64_le,
-    pub fs: u64_le,
-    pub gs: u64_le,
+pub(crate) struct siginfo_t<BO: ByteOrder> {
+    pub si_signo: I32<BO>,
+    pub si_errno: I32<BO>,
+    pub si_code: I32<BO>,
+    _pad1: [u8; 4],
+    /// Signal-specific fields. For fault signals, the first 8 bytes are the
+    /// faulting address (`si_addr`).
+    pub sifields: [u8; 112],
 }
 
-impl CType for elf_gregset_t {
+impl<BO: ByteOrder> CType for siginfo_t<BO> {
+    const NAME: &'static str = "siginfo_t";
+}
+
+#[derive(Clone, Copy, Debug, View)]
+#[repr(C)]
+pub(crate) struct elf_gregset_t<BO: ByteOrder> {
+    pub r15: U64<BO>,
+    pub r14: U64<BO>,
+    pub r13: U64<BO>,
+    pub r12: U64<BO>,
+    pub bp: U64<BO>,
+    pub bx: U64<BO>,
+    pub r11: U64<BO>,
+    pub r10: U64<BO>,
+    pub r9: U64<BO>,
+    pub r8: U64<BO>,
+    pub ax: U64<BO>,
+    pub cx: U64<BO>,
+    pub dx: U64<BO>,
+    pub si: U64<BO>,
+    pub di: U64<BO>,
+    pub orig_ax: U64<BO>,
+    pub ip: U64<BO>,
+    pub cs: U64<BO>,
+    pub flags: U64<BO>,
+    pub sp: U64<BO>,
+    pub ss: U64<BO>,
+    pub fs_base: U64<BO>,
+    pub gs_base: U64<BO>,
+    pub ds: U64<BO>,
+    pub es: U64<BO>,
+    pub fs: U64<BO>,
+    pub gs: U64<BO>,
+}
+
+impl<BO: ByteOrder> CType for elf_gregset_t<BO> {
     const NAME: &'static str = "elf_gregset_t";
 }
 
 #[derive(Clone, Copy, Debug, View)]
 #[repr(C)]
-pub(crate) struct __kernel_old_timeval {
-    pub tv_sec: i64_le,
-    pub tv_usec: i64_le,
+pub(crate) struct __kernel_old_timeval<BO: ByteOrder> {
+    pub tv_sec: I64<BO>,
+    pub tv_usec: I64<BO>,
 }
 
-impl CType for __kernel_old_timeval {
+impl<BO: ByteOrder> CType for __kernel_old_timeval<BO> {
     const NAME: &'static str = "__kernel_old_timeval";
 }
 
-mod constants {
+pub(crate) mod constants {
     /// 64-bit file class.
     pub const ELFCLASS64: u8 = 2;
 
     /// Little-endian data encoding.
     pub const ELFDATA2LSB: u8 = 1;
+    /// Big-endian data encoding.
+    pub const ELFDATA2MSB: u8 = 2;
 
     /// Current file format version.
     pub const EV_CURRENT: u8 = 1;
 
     /// System V ABI.
     pub const ELFOSABI_SYSV: u8 = 0;
+    /// Linux ABI.
+    pub const ELFOSABI_LINUX: u8 = 3;
 
     /// Core file type.
     pub const ET_CORE: u16 = 4;
@@ -285,15 +343,82 @@ mod constants {
     /// AMD x86-64 machine architecture.
     pub const EM_X86_64: u16 = 62;
 
+    /// Marker for an `e_phnum` that is too large to fit into the ELF header.
+    /// The real number is stored in the `sh_info` field of section header 0.
+    pub const PN_XNUM: u16 = 0xffff;
+
     /// Loadable segment.
     pub const PT_LOAD: u32 = 1;
+    /// Dynamic linking information.
+    pub const PT_DYNAMIC: u32 = 2;
     /// Note sections.
     pub const PT_NOTE: u32 = 4;
+    /// Location of the program header table itself.
+    pub const PT_PHDR: u32 = 6;
+
+    /// End of the dynamic section.
+    pub const DT_NULL: u64 = 0;
+    /// Address of the dynamic linker's debugging structure (`r_debug`).
+    pub const DT_DEBUG: u64 = 21;
+
+    /// Section holding notes.
+    pub const SHT_NOTE: u32 = 7;
+
+    /// Segment is executable.
+    pub const PF_X: u32 = 1 << 0;
+    /// Segment is writable.
+    pub const PF_W: u32 = 1 << 1;
+    /// Segment is readable.
+    pub const PF_R: u32 = 1 << 2;
 
     /// Thread status.
     pub const NT_PRSTATUS: u32 = 1;
+    /// Floating point registers.
+    pub const NT_PRFPREG: u32 = 2;
     /// Process info.
     pub const NT_PRPSINFO: u32 = 3;
+    /// Auxiliary vector.
+    pub const NT_AUXV: u32 = 6;
+    /// Signal info.
+    pub const NT_SIGINFO: u32 = 0x5349_4749;
     /// File map.
     pub const NT_FILE: u32 = 0x4649_4c45;
+    /// x86 I/O permission bitmap.
+    pub const NT_386_IOPERM: u32 = 0x201;
+    /// x86 extended state (XSAVE area).
+    pub const NT_X86_XSTATE: u32 = 0x202;
+
+    /// GNU build ID.
+    pub const NT_GNU_BUILD_ID: u32 = 3;
+    /// GNU program properties.
+    pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+    /// GDB target description.
+    pub const NT_GDB_TDESC: u32 = 0xff;
+
+    /// Full command line of the process, with NUL-terminated arguments as in
+    /// `/proc/<pid>/cmdline`.
+    ///
+    /// This note is not written by the kernel and has no standardized type.
+    /// The value is ASCII `CMDL` and can be overridden through
+    /// `ParseOptions::cmdline_note_type`.
+    pub const NT_CMDLINE: u32 = 0x434d_444c;
+
+    /// End of the auxiliary vector.
+    pub const AT_NULL: u64 = 0;
+    /// Address of the program headers of the executable.
+    pub const AT_PHDR: u64 = 3;
+    /// Size of a program header entry.
+    pub const AT_PHENT: u64 = 4;
+    /// Number of program headers.
+    pub const AT_PHNUM: u64 = 5;
+    /// Address of the filename of the executed program.
+    pub const AT_EXECFN: u64 = 31;
+
+    /// x86 features that are enabled if all input objects enable them.
+    pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+    /// Indirect branch tracking.
+    pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 1 << 0;
+    /// Shadow stack.
+    pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 1 << 1;
 }
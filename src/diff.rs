@@ -0,0 +1,190 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::core::{Core, ProcessInfo, ThreadId};
+use crate::registers::RegisterName;
+
+/// The structural differences between two cores of the same process.
+///
+/// Segment contents are not compared, only the address ranges of segments.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CoreDiff<'a> {
+    /// Names of the [`ProcessInfo`] fields that differ.
+    pub process_changes: Vec<&'static str>,
+    /// Register changes of threads present in both cores.
+    pub thread_changes: Vec<ThreadDiff>,
+    /// Threads present only in the new core.
+    pub added_threads: Vec<ThreadId>,
+    /// Threads present only in the old core.
+    pub removed_threads: Vec<ThreadId>,
+    /// Paths of files mapped only in the new core.
+    pub added_modules: Vec<&'a [u8]>,
+    /// Paths of files mapped only in the old core.
+    pub removed_modules: Vec<&'a [u8]>,
+    /// Address ranges of segments present only in the new core.
+    pub added_ranges: Vec<Range<usize>>,
+    /// Address ranges of segments present only in the old core.
+    pub removed_ranges: Vec<Range<usize>>,
+}
+
+impl CoreDiff<'_> {
+    /// Return whether no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.process_changes.is_empty()
+            && self.thread_changes.is_empty()
+            && self.added_threads.is_empty()
+            && self.removed_threads.is_empty()
+            && self.added_modules.is_empty()
+            && self.removed_modules.is_empty()
+            && self.added_ranges.is_empty()
+            && self.removed_ranges.is_empty()
+    }
+}
+
+impl fmt::Debug for CoreDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let paths = |paths: &[&[u8]]| -> Vec<String> {
+            paths
+                .iter()
+                .map(|p| String::from_utf8_lossy(p).into_owned())
+                .collect()
+        };
+
+        f.debug_struct("CoreDiff")
+            .field("process_changes", &self.process_changes)
+            .field("thread_changes", &self.thread_changes)
+            .field("added_threads", &self.added_threads)
+            .field("removed_threads", &self.removed_threads)
+            .field("added_modules", &paths(&self.added_modules))
+            .field("removed_modules", &paths(&self.removed_modules))
+            .field("added_ranges", &self.added_ranges)
+            .field("removed_ranges", &self.removed_ranges)
+            .finish()
+    }
+}
+
+/// The register changes of a single thread.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadDiff {
+    pub id: ThreadId,
+    pub registers: Vec<RegisterChange>,
+}
+
+/// A register whose value differs between two cores.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RegisterChange {
+    pub name: RegisterName,
+    pub old: u64,
+    pub new: u64,
+}
+
+impl fmt::Debug for RegisterChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisterChange")
+            .field("name", &self.name)
+            .field("old", &format_args!("{:#018x}", self.old))
+            .field("new", &format_args!("{:#018x}", self.new))
+            .finish()
+    }
+}
+
+impl<'d> Core<'d> {
+    /// Compare this core with a newer core of the same process.
+    ///
+    /// Threads are matched by their ID, files by their path, and segments by
+    /// their address range.
+    pub fn diff<'a>(&'a self, other: &'a Core) -> CoreDiff<'a> {
+        // Insert in reverse so that the first of several threads with the same
+        // ID is matched.
+        let new_threads: HashMap<_, _> = other.threads.iter().rev().map(|t| (t.id(), t)).collect();
+        let old_ids: HashSet<_> = self.threads.iter().map(|t| t.id()).collect();
+
+        let mut thread_changes = Vec::new();
+        let mut removed_threads = Vec::new();
+        for old in &self.threads {
+            let Some(new) = new_threads.get(&old.id()) else {
+                removed_threads.push(old.id());
+                continue;
+            };
+
+            let registers: Vec<_> = old
+                .registers
+                .diff(&new.registers)
+                .into_iter()
+                .map(|(name, old, new)| RegisterChange { name, old, new })
+                .collect();
+            if !registers.is_empty() {
+                thread_changes.push(ThreadDiff {
+                    id: old.id(),
+                    registers,
+                });
+            }
+        }
+        let added_threads = other
+            .threads
+            .iter()
+            .filter(|new| !old_ids.contains(&new.id()))
+            .map(|t| t.id())
+            .collect();
+
+        let old_modules = module_paths(self);
+        let new_modules = module_paths(other);
+        let old_ranges: Vec<_> = self.segments.iter().map(|s| s.range()).collect();
+        let new_ranges: Vec<_> = other.segments.iter().map(|s| s.range()).collect();
+
+        CoreDiff {
+            process_changes: process_changes(&self.process, &other.process),
+            thread_changes,
+            added_threads,
+            removed_threads,
+            added_modules: difference(&new_modules, &old_modules),
+            removed_modules: difference(&old_modules, &new_modules),
+            added_ranges: difference(&new_ranges, &old_ranges),
+            removed_ranges: difference(&old_ranges, &new_ranges),
+        }
+    }
+}
+
+/// Return the distinct paths of the files mapped into the given core.
+fn module_paths<'a>(core: &'a Core) -> Vec<&'a [u8]> {
+    let mut paths: Vec<_> = core.file_map.iter().map(|m| m.file_path).collect();
+    paths.sort_unstable();
+    paths.dedup();
+    paths
+}
+
+/// Return the items of `a` that are not in `b`, in the order of `a`.
+fn difference<T: Clone + Eq + Hash>(a: &[T], b: &[T]) -> Vec<T> {
+    let b: HashSet<_> = b.iter().collect();
+    a.iter().filter(|x| !b.contains(x)).cloned().collect()
+}
+
+fn process_changes(old: &ProcessInfo, new: &ProcessInfo) -> Vec<&'static str> {
+    let fields = [
+        ("state", old.state != new.state),
+        ("state_name", old.state_name != new.state_name),
+        ("zombie", old.zombie != new.zombie),
+        ("nice", old.nice != new.nice),
+        ("flags", old.flags != new.flags),
+        ("uid", old.uid != new.uid),
+        ("gid", old.gid != new.gid),
+        ("pid", old.pid != new.pid),
+        ("ppid", old.ppid != new.ppid),
+        ("pgrp", old.pgrp != new.pgrp),
+        ("sid", old.sid != new.sid),
+        ("file_name", old.file_name != new.file_name),
+        ("command", old.command != new.command),
+        (
+            "current_signal",
+            old.current_signal() != new.current_signal(),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(name, _)| name)
+        .collect()
+}
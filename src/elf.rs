@@ -1,36 +1,134 @@
-use crate::ctypes::{CType, Elf64_Ehdr, Elf64_Nhdr, Elf64_Phdr, PT_NOTE};
-use crate::error::ParseError;
-use crate::read::ReadExt;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use crate::ctypes::{
+    CType, Elf64_Ehdr, Elf64_Nhdr, Elf64_Phdr, Elf64_Shdr, ELFDATA2LSB, ELFDATA2MSB, NT_PRPSINFO,
+    PN_XNUM, PT_LOAD, PT_NOTE, SHT_NOTE,
+};
+use crate::error::{ParseError, ParseWarning};
+use crate::note::{RawNote, CORE, LINUX};
+use crate::options::ParseOptions;
+use crate::read::{with_byte_order, ByteOrder, ReadExt};
 use crate::util::trim_c_string;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct Elf<'d> {
+    byte_order: ByteOrder,
     program_headers: Vec<ProgramHeader>,
+    /// The file ranges of the notes, in visiting order.
+    note_ranges: Vec<NoteRange>,
+    /// Whether to drop notes identical to a note in an earlier range.
+    dedup_notes: bool,
     notes: Vec<Note<'d>>,
     data: &'d [u8],
+    warnings: Vec<ParseWarning>,
 }
 
 impl<'d> Elf<'d> {
-    pub fn parse(data: &'d [u8]) -> Result<Self, ParseError> {
-        let header = parse_header(data)?;
+    pub fn parse(data: &'d [u8], options: &ParseOptions) -> Result<Self, ParseError> {
+        let byte_order = detect_byte_order(data)?;
+        with_byte_order!(byte_order, BO => Self::parse_as::<BO>(data, byte_order, options))
+    }
+
+    fn parse_as<BO: byteorder::ByteOrder>(
+        data: &'d [u8],
+        byte_order: ByteOrder,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let mut header = parse_header::<BO>(data)?;
+        if header.ph_count == PN_XNUM.into() {
+            header.ph_count = parse_extended_ph_count::<BO>(data, header.sh_offset)?;
+        }
+
+        let ph_data = data
+            .get(header.ph_offset..)
+            .ok_or(ParseError::HeaderTableOutOfBounds {
+                table: "program header",
+                offset: header.ph_offset,
+            })?;
+        let program_headers = parse_program_headers::<BO>(ph_data, header.ph_count)?;
+        if options.verify_alignment {
+            program_headers.iter().try_for_each(verify_alignment)?;
+        }
 
-        let ph_data = data.get(header.ph_offset..).ok_or_else(|| {
-            format!(
-                "program header table offset is out of bounds: {:#x}",
-                header.ph_offset,
+        if let Some(align) = options.note_alignment {
+            if align != 4 && align != 8 {
+                return Err(ParseError::InvalidNoteAlignment { align });
+            }
+        }
+
+        // Section headers are only used to find additional notes, so an
+        // invalid section header table is not fatal.
+        let mut warnings = Vec::new();
+        let section_headers = if header.sh_offset != 0 && header.sh_count != 0 {
+            parse_section_headers::<BO>(data, header.sh_offset, header.sh_count).unwrap_or_else(
+                |e| {
+                    let reason = e.into_message();
+                    warnings.push(ParseWarning::InvalidSectionHeaders { reason });
+                    Vec::new()
+                },
             )
-        })?;
-        let program_headers = parse_program_headers(ph_data, header.ph_count)?;
+        } else {
+            Vec::new()
+        };
 
-        let notes = parse_notes(&program_headers, data)?;
+        let mut note_ranges = find_note_ranges::<BO>(
+            &program_headers,
+            &section_headers,
+            data,
+            options,
+            &mut warnings,
+        )?;
+        if options.find_notes_in_load_segments {
+            let has_prpsinfo = visit_note_ranges::<BO, _>(&note_ranges, data, false, |n| {
+                if n.matches(&[CORE, LINUX], NT_PRPSINFO) {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+            if has_prpsinfo.is_continue() {
+                note_ranges.extend(find_note_ranges_in_load_segments::<BO>(
+                    &program_headers,
+                    data,
+                ));
+            }
+        }
+
+        let mut notes = Vec::new();
+        let _ = visit_note_ranges::<BO, _>(&note_ranges, data, options.dedup_notes, |note| {
+            notes.push(note);
+            ControlFlow::Continue(())
+        });
 
         Ok(Self {
+            byte_order,
             program_headers,
+            note_ranges,
+            dedup_notes: options.dedup_notes,
             notes,
             data,
+            warnings,
         })
     }
 
+    /// An ELF file without headers, notes, or data.
+    pub fn empty() -> Self {
+        Self {
+            byte_order: ByteOrder::Little,
+            program_headers: Vec::new(),
+            note_ranges: Vec::new(),
+            dedup_notes: false,
+            notes: Vec::new(),
+            data: &[],
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
     pub fn iter_program_headers(&self, type_: u32) -> impl Iterator<Item = &ProgramHeader> {
         self.program_headers
             .iter()
@@ -38,129 +136,540 @@ impl<'d> Elf<'d> {
     }
 
     pub fn read_segment(&self, ph: &ProgramHeader) -> Result<&'d [u8], ParseError> {
-        ph.get_data(self.data)
-            .ok_or_else(|| format!("program header has invalid file range: {ph:?}").into())
+        ph.get_data(self.data).ok_or_else(|| ph.out_of_bounds())
     }
 
-    pub fn iter_notes<'a>(
-        &'a self,
-        name: &'a [u8],
-        type_: u32,
-    ) -> impl Iterator<Item = &'d [u8]> + 'a {
-        self.notes
-            .iter()
-            .filter(move |n| n.name == name && n.type_ == type_)
-            .map(|n| n.desc)
+    /// Read the data of the given segment, clamped to the end of the file.
+    pub fn read_segment_truncated(&self, ph: &ProgramHeader) -> &'d [u8] {
+        let start = ph.file_offset.min(self.data.len());
+        let end = ph
+            .file_offset
+            .saturating_add(ph.file_size)
+            .min(self.data.len());
+        &self.data[start..end]
+    }
+
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
     }
 
-    pub fn get_note(&self, name: &[u8], type_: u32) -> Option<&'d [u8]> {
-        self.iter_notes(name, type_).next()
+    pub fn data(&self) -> &'d [u8] {
+        self.data
+    }
+
+    pub fn get_note(&self, names: &[&[u8]], type_: u32) -> Option<&'d [u8]> {
+        self.get_note_entry(names, type_).map(|n| n.desc)
+    }
+
+    pub fn notes(&self) -> &[Note<'d>] {
+        &self.notes
+    }
+
+    /// Call `f` for each note in parse order, until it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// The notes are parsed one at a time from the file data, so stopping
+    /// early skips parsing the remaining notes.
+    pub fn visit_notes<F>(&self, mut f: F) -> ControlFlow<()>
+    where
+        F: FnMut(&RawNote<'d>) -> ControlFlow<()>,
+    {
+        with_byte_order!(self.byte_order, BO => {
+            visit_note_ranges::<BO, _>(&self.note_ranges, self.data, self.dedup_notes, |note| {
+                f(&note.to_raw())
+            })
+        })
+    }
+
+    pub fn get_note_entry(&self, names: &[&[u8]], type_: u32) -> Option<&Note<'d>> {
+        self.notes.iter().find(|n| n.matches(names, type_))
+    }
+}
+
+/// Determine the byte order of the ELF file from its `e_ident` bytes.
+fn detect_byte_order(data: &[u8]) -> Result<ByteOrder, ParseError> {
+    match data.get(5) {
+        Some(&ELFDATA2LSB) => Ok(ByteOrder::Little),
+        Some(&ELFDATA2MSB) => Ok(ByteOrder::Big),
+        Some(other) => Err(ParseError::InvalidHeader {
+            name: Elf64_Ehdr::<byteorder::LE>::NAME,
+            reason: format!("invalid e_ident.data value: {other}"),
+        }),
+        None => Err(ParseError::TruncatedHeader {
+            name: Elf64_Ehdr::<byteorder::LE>::NAME,
+            size: Elf64_Ehdr::<byteorder::LE>::SIZE,
+            available: data.len(),
+        }),
     }
 }
 
-fn parse_header(data: &[u8]) -> Result<Header, ParseError> {
-    Elf64_Ehdr::parse(data).map(Into::into)
+fn parse_header<BO: byteorder::ByteOrder>(data: &[u8]) -> Result<Header, ParseError> {
+    Elf64_Ehdr::<BO>::parse(data).map(Into::into)
 }
 
-fn parse_program_headers(data: &[u8], count: usize) -> Result<Vec<ProgramHeader>, ParseError> {
-    let phdrs = Elf64_Phdr::parse_n(data, count)?;
-    let phs = phdrs.iter().map(Into::into).collect();
+/// Read the real number of program headers from section header 0, for files
+/// whose `e_phnum` is `PN_XNUM`.
+fn parse_extended_ph_count<BO: byteorder::ByteOrder>(
+    data: &[u8],
+    sh_offset: usize,
+) -> Result<usize, ParseError> {
+    if sh_offset == 0 {
+        return Err(ParseError::MissingExtendedPhCount);
+    }
+
+    let sh_data = data
+        .get(sh_offset..)
+        .ok_or(ParseError::HeaderTableOutOfBounds {
+            table: "section header",
+            offset: sh_offset,
+        })?;
+    let shdr = Elf64_Shdr::<BO>::parse(sh_data)?;
+    Ok(shdr.sh_info.to_int() as usize)
+}
+
+fn parse_program_headers<BO: byteorder::ByteOrder>(
+    data: &[u8],
+    count: usize,
+) -> Result<Vec<ProgramHeader>, ParseError> {
+    let phdrs = Elf64_Phdr::<BO>::parse_n(data, count)?;
+    let phs = phdrs
+        .iter()
+        .enumerate()
+        .map(|(index, phdr)| ProgramHeader {
+            index,
+            ..phdr.into()
+        })
+        .collect();
     Ok(phs)
 }
 
-fn parse_notes<'d>(phs: &[ProgramHeader], data: &'d [u8]) -> Result<Vec<Note<'d>>, ParseError> {
-    let mut notes = Vec::new();
-    for ph in phs {
-        if ph.type_ != PT_NOTE {
+/// Verify that the addresses of the given program header are aligned to its
+/// alignment.
+fn verify_alignment(ph: &ProgramHeader) -> Result<(), ParseError> {
+    // Values 0 and 1 both mean that no alignment is required.
+    let align = ph.align.max(1);
+
+    if !ph.memory_address.is_multiple_of(align) {
+        return Err(ParseError::UnalignedSegment {
+            field: "p_vaddr",
+            address: ph.memory_address,
+        });
+    }
+    if !ph.physical_address.is_multiple_of(align) {
+        return Err(ParseError::UnalignedSegment {
+            field: "p_paddr",
+            address: ph.physical_address,
+        });
+    }
+
+    Ok(())
+}
+
+fn parse_section_headers<BO: byteorder::ByteOrder>(
+    data: &[u8],
+    offset: usize,
+    count: usize,
+) -> Result<Vec<SectionHeader>, ParseError> {
+    let sh_data = data
+        .get(offset..)
+        .ok_or(ParseError::HeaderTableOutOfBounds {
+            table: "section header",
+            offset,
+        })?;
+    let shdrs = Elf64_Shdr::<BO>::parse_n(sh_data, count)?;
+    let shs = shdrs.iter().map(Into::into).collect();
+    Ok(shs)
+}
+
+/// Find the notes in all `PT_NOTE` segments, followed by the notes in any
+/// `SHT_NOTE` sections not already covered by a `PT_NOTE` segment.
+///
+/// Returns the file ranges of the notes, whose notes are all parsed once to
+/// validate them. Segments are visited in program header order, and
+/// sections in section header order. If no `note_alignment` is configured,
+/// the note alignment of `PT_NOTE` segments is derived from their `p_align`,
+/// and that of sections defaults to 4.
+fn find_note_ranges<BO: byteorder::ByteOrder>(
+    phs: &[ProgramHeader],
+    shs: &[SectionHeader],
+    data: &[u8],
+    options: &ParseOptions,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Vec<NoteRange>, ParseError> {
+    let note_phs: Vec<_> = phs.iter().filter(|ph| ph.type_ == PT_NOTE).collect();
+
+    let mut ranges = Vec::new();
+    for ph in &note_phs {
+        let note_data = ph.get_data(data).ok_or_else(|| ph.out_of_bounds())?;
+        let align = options
+            .note_alignment
+            .unwrap_or(if ph.align == 8 { 8 } else { 4 });
+        validate_note_data::<BO>(note_data, ph.file_offset, align, warnings)?;
+        ranges.push(NoteRange {
+            offset: ph.file_offset,
+            size: ph.file_size,
+            align,
+        });
+    }
+
+    for sh in shs {
+        if sh.type_ != SHT_NOTE {
             continue;
         }
 
-        let mut note_data = ph
-            .get_data(data)
-            .ok_or_else(|| format!("program header has invalid file range: {ph:?}"))?;
+        // The file ranges of the `PT_NOTE` segments have been validated
+        // above, so only the section range can overflow here. Both ranges
+        // are half-open.
+        let sh_end = sh.file_offset.checked_add(sh.file_size);
+        let covered = note_phs.iter().any(|ph| {
+            ph.file_offset <= sh.file_offset
+                && sh_end.is_some_and(|end| end <= ph.file_offset + ph.file_size)
+        });
+        if covered {
+            continue;
+        }
 
-        while !note_data.is_empty() {
-            let (note, rest) = parse_note(note_data)?;
-            notes.push(note);
-            note_data = rest;
+        // Like the section header table, note sections are optional, so
+        // invalid ones are skipped.
+        let Some(note_data) = sh.get_data(data) else {
+            warnings.push(ParseWarning::InvalidNoteSection {
+                offset: sh.file_offset,
+                reason: "invalid file range".to_string(),
+            });
+            continue;
+        };
+        let align = options.note_alignment.unwrap_or(4);
+        match validate_note_data::<BO>(note_data, sh.file_offset, align, warnings) {
+            Ok(()) => ranges.push(NoteRange {
+                offset: sh.file_offset,
+                size: sh.file_size,
+                align,
+            }),
+            Err(e) => warnings.push(ParseWarning::InvalidNoteSection {
+                offset: sh.file_offset,
+                reason: e.into_message(),
+            }),
         }
     }
 
-    Ok(notes)
+    Ok(ranges)
 }
+/// Find the notes at the start of `PT_LOAD` segments, as written by some
+/// non-conformant dumpers, and return their file ranges.
+///
+/// A segment is only considered to contain notes if it starts with a `CORE`
+/// or `LINUX` note. Scanning a segment stops at the first entry that fails
+/// to parse, has no owner name, such as zero padding, or has non-zero
+/// padding.
+fn find_note_ranges_in_load_segments<BO: byteorder::ByteOrder>(
+    phs: &[ProgramHeader],
+    data: &[u8],
+) -> Vec<NoteRange> {
+    let mut ranges = Vec::new();
+    for ph in phs.iter().filter(|ph| ph.type_ == PT_LOAD) {
+        let Some(segment_data) = ph.get_data(data) else {
+            continue;
+        };
 
-fn parse_note(data: &[u8]) -> Result<(Note<'_>, &[u8]), ParseError> {
-    let wrap_error = |e| format!("note: {e}");
-    let padding = |n| (4 - (n % 4)) % 4;
+        let mut rest = segment_data;
+        loop {
+            let offset = ph.file_offset + (segment_data.len() - rest.len());
+            let Ok((note, next, padding_zeroed)) = parse_note::<BO>(rest, offset, 4) else {
+                break;
+            };
 
-    let nhdr = Elf64_Nhdr::parse(data)?;
-    let mut data = &data[Elf64_Nhdr::SIZE..];
+            let is_first = rest.len() == segment_data.len();
+            if note.name.is_empty()
+                || !padding_zeroed
+                || (is_first && ![CORE, LINUX].contains(&note.name))
+            {
+                break;
+            }
+
+            rest = next;
+        }
+
+        let size = segment_data.len() - rest.len();
+        if size > 0 {
+            ranges.push(NoteRange {
+                offset: ph.file_offset,
+                size,
+                align: 4,
+            });
+        }
+    }
+
+    ranges
+}
+
+/// Call `f` for each note in the given ranges, until it returns
+/// [`ControlFlow::Break`].
+///
+/// With `dedup`, notes identical to a note in an earlier range are skipped.
+/// Identical notes within the same range are kept.
+fn visit_note_ranges<'d, BO, F>(
+    ranges: &[NoteRange],
+    data: &'d [u8],
+    dedup: bool,
+    mut f: F,
+) -> ControlFlow<()>
+where
+    BO: byteorder::ByteOrder,
+    F: FnMut(Note<'d>) -> ControlFlow<()>,
+{
+    let mut seen = HashSet::new();
+    for range in ranges {
+        let Some(range_data) = data.get(range.offset..range.offset + range.size) else {
+            continue;
+        };
+
+        let mut range_keys = Vec::new();
+        let result = visit_note_data::<BO, _>(range_data, range.offset, range.align, |note, _| {
+            if dedup {
+                let key = (note.name, note.type_, note.desc);
+                if seen.contains(&key) {
+                    return ControlFlow::Continue(());
+                }
+                range_keys.push(key);
+            }
+            f(note)
+        });
+        seen.extend(range_keys);
+
+        // The ranges were validated when they were found, so parsing them
+        // doesn't fail.
+        if let Ok(ControlFlow::Break(())) = result {
+            return ControlFlow::Break(());
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Parse the notes in `data`, which starts at file offset `start`, and
+/// record a warning for each note with non-zero padding.
+fn validate_note_data<BO: byteorder::ByteOrder>(
+    data: &[u8],
+    start: usize,
+    align: usize,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), ParseError> {
+    visit_note_data::<BO, _>(data, start, align, |note, padding_zeroed| {
+        if !padding_zeroed {
+            warnings.push(ParseWarning::NotePadding {
+                offset: note.offset,
+            });
+        }
+        ControlFlow::Continue(())
+    })
+    .map(|_| ())
+}
+
+/// Call `f` for each note in `data`, which starts at file offset `start`,
+/// until it returns [`ControlFlow::Break`].
+///
+/// Besides the note, `f` receives whether the padding bytes of the note were
+/// all zero. The notes are parsed one at a time, so stopping early skips
+/// parsing the remaining data.
+fn visit_note_data<'d, BO, F>(
+    data: &'d [u8],
+    start: usize,
+    align: usize,
+    mut f: F,
+) -> Result<ControlFlow<()>, ParseError>
+where
+    BO: byteorder::ByteOrder,
+    F: FnMut(Note<'d>, bool) -> ControlFlow<()>,
+{
+    let mut note_data = data;
+    while !note_data.is_empty() {
+        let offset = start + (data.len() - note_data.len());
+        let (note, rest, padding_zeroed) = parse_note::<BO>(note_data, offset, align)?;
+        if f(note, padding_zeroed).is_break() {
+            return Ok(ControlFlow::Break(()));
+        }
+        note_data = rest;
+    }
+
+    Ok(ControlFlow::Continue(()))
+}
+
+/// Parse the note at the start of `data`, which is at file offset `offset`.
+///
+/// Returns the note, the data following it, and whether the padding bytes of
+/// the note were all zero.
+fn parse_note<BO: byteorder::ByteOrder>(
+    data: &[u8],
+    offset: usize,
+    align: usize,
+) -> Result<(Note<'_>, &[u8], bool), ParseError> {
+    let wrap_error = |reason: String| ParseError::InvalidNote {
+        note: "note",
+        offset,
+        reason,
+    };
+    let padding = |n| (align - (n % align)) % align;
+
+    let nhdr = Elf64_Nhdr::<BO>::parse(data).map_err(|e| wrap_error(e.into_message()))?;
+    let mut data = &data[Elf64_Nhdr::<BO>::SIZE..];
 
     let name_size = nhdr.n_namesz.to_int() as usize;
     let desc_size = nhdr.n_descsz.to_int() as usize;
-    let name_padding = padding(name_size);
+    // Padding is relative to the start of the note, so the descriptor
+    // offset includes the header size. This also holds for notes without an
+    // owner name, whose descriptor directly follows the header if that is
+    // already aligned.
+    let name_padding = padding(Elf64_Nhdr::<BO>::SIZE + name_size);
     let desc_padding = padding(desc_size);
 
-    let name = data.read_slice(name_size).map_err(wrap_error)?;
-    let _pad = data.read_slice(name_padding).map_err(wrap_error)?;
-    let desc = data.read_slice(desc_size).map_err(wrap_error)?;
-    let _pad = data.read_slice(desc_padding).map_err(wrap_error)?;
+    // Make sure the note fits into the remaining data before reading it, so
+    // a corrupt size is reported as such instead of as a generic read error.
+    let note_size = name_size + name_padding + desc_size + desc_padding;
+    if note_size > data.len() {
+        return Err(ParseError::NoteTooLarge {
+            offset,
+            size: Elf64_Nhdr::<BO>::SIZE + note_size,
+            available: Elf64_Nhdr::<BO>::SIZE + data.len(),
+        });
+    }
+
+    let read_error = |e: &str| wrap_error(e.to_string());
+    let name = data.read_slice(name_size).map_err(read_error)?;
+    let name_pad = data.read_slice(name_padding).map_err(read_error)?;
+    let desc = data.read_slice(desc_size).map_err(read_error)?;
+    let desc_pad = data.read_slice(desc_padding).map_err(read_error)?;
+    let padding_zeroed = name_pad.iter().chain(desc_pad).all(|&b| b == 0);
 
     let note = Note {
         type_: nhdr.n_type.to_int(),
         name: trim_c_string(name),
         desc,
+        offset,
     };
-    Ok((note, data))
+    Ok((note, data, padding_zeroed))
 }
 
 #[derive(Debug)]
 struct Header {
     ph_offset: usize,
     ph_count: usize,
+    sh_offset: usize,
+    sh_count: usize,
 }
 
-impl From<&Elf64_Ehdr> for Header {
-    fn from(ehdr: &Elf64_Ehdr) -> Self {
+impl<BO: byteorder::ByteOrder> From<&Elf64_Ehdr<BO>> for Header {
+    fn from(ehdr: &Elf64_Ehdr<BO>) -> Self {
         Self {
             ph_offset: ehdr.e_phoff.to_int() as usize,
             ph_count: ehdr.e_phnum.to_int() as usize,
+            sh_offset: ehdr.e_shoff.to_int() as usize,
+            sh_count: ehdr.e_shnum.to_int() as usize,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct ProgramHeader {
+    /// Index of this header in the program header table.
+    pub index: usize,
     pub type_: u32,
+    pub flags: u32,
     pub file_offset: usize,
     pub file_size: usize,
     pub memory_address: usize,
+    pub physical_address: usize,
     pub memory_size: usize,
+    pub align: usize,
 }
 
 impl ProgramHeader {
     fn get_data<'d>(&self, data: &'d [u8]) -> Option<&'d [u8]> {
         let start = self.file_offset;
-        let end = start + self.file_size;
+        let end = start.checked_add(self.file_size)?;
         data.get(start..end)
     }
+
+    fn out_of_bounds(&self) -> ParseError {
+        ParseError::SegmentOutOfBounds {
+            index: self.index,
+            offset: self.file_offset,
+            size: self.file_size,
+        }
+    }
 }
 
-impl From<&Elf64_Phdr> for ProgramHeader {
-    fn from(phdr: &Elf64_Phdr) -> Self {
+impl<BO: byteorder::ByteOrder> From<&Elf64_Phdr<BO>> for ProgramHeader {
+    fn from(phdr: &Elf64_Phdr<BO>) -> Self {
         ProgramHeader {
+            index: 0,
             type_: phdr.p_type.to_int(),
+            flags: phdr.p_flags.to_int(),
             file_offset: phdr.p_offset.to_int() as usize,
             file_size: phdr.p_filesz.to_int() as usize,
             memory_address: phdr.p_vaddr.to_int() as usize,
+            physical_address: phdr.p_paddr.to_int() as usize,
             memory_size: phdr.p_memsz.to_int() as usize,
+            align: phdr.p_align.to_int() as usize,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct SectionHeader {
+    pub type_: u32,
+    pub file_offset: usize,
+    pub file_size: usize,
+}
+
+impl SectionHeader {
+    fn get_data<'d>(&self, data: &'d [u8]) -> Option<&'d [u8]> {
+        let start = self.file_offset;
+        let end = start.checked_add(self.file_size)?;
+        data.get(start..end)
+    }
+}
+
+impl<BO: byteorder::ByteOrder> From<&Elf64_Shdr<BO>> for SectionHeader {
+    fn from(shdr: &Elf64_Shdr<BO>) -> Self {
+        SectionHeader {
+            type_: shdr.sh_type.to_int(),
+            file_offset: shdr.sh_offset.to_int() as usize,
+            file_size: shdr.sh_size.to_int() as usize,
+        }
+    }
+}
+
+/// A file range containing notes that all parse successfully.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct NoteRange {
+    offset: usize,
+    size: usize,
+    align: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct Note<'d> {
-    type_: u32,
-    name: &'d [u8],
-    desc: &'d [u8],
+    pub type_: u32,
+    pub name: &'d [u8],
+    pub desc: &'d [u8],
+    /// File offset of the note header.
+    pub offset: usize,
+}
+
+impl<'d> Note<'d> {
+    /// Convert this note into its public representation.
+    pub fn to_raw(&self) -> RawNote<'d> {
+        RawNote {
+            name: self.name,
+            type_: self.type_,
+            desc: self.desc,
+            offset: self.offset,
+        }
+    }
+
+    /// Return whether this note has one of the given owner names and the
+    /// given type.
+    fn matches(&self, names: &[&[u8]], type_: u32) -> bool {
+        self.type_ == type_ && names.contains(&self.name)
+    }
 }
@@ -1,18 +1,215 @@
 use std::fmt;
 
-#[derive(Debug)]
-pub struct ParseError(String);
+use crate::core::SegmentId;
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "parse error: {}", self.0)
+/// An error that prevented parsing a core file.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// A structure of the given type needs `size` bytes, but only `available`
+    /// bytes are left.
+    #[error("parse error: {name}: not enough data ({available:#x} of {size:#x} bytes)")]
+    TruncatedHeader {
+        name: &'static str,
+        size: usize,
+        available: usize,
+    },
+    /// A field of a structure of the given type has an unsupported value.
+    #[error("parse error: {name}: {reason}")]
+    InvalidHeader { name: &'static str, reason: String },
+    /// The program or section header table starts beyond the end of the file.
+    #[error("parse error: {table} table offset is out of bounds: {offset:#x}")]
+    HeaderTableOutOfBounds { table: &'static str, offset: usize },
+    /// `e_phnum` is `PN_XNUM`, but there is no section header holding the
+    /// real program header count.
+    #[error("parse error: e_phnum is PN_XNUM, but there is no section header table")]
+    MissingExtendedPhCount,
+    /// The address of a segment is not aligned to its alignment.
+    #[error("parse error: Elf64_Phdr: unaligned {field} value: {address:#x}")]
+    UnalignedSegment { field: &'static str, address: usize },
+    /// The file range of the segment with the given program header index
+    /// extends beyond the end of the file.
+    #[error(
+        "parse error: program header {index} has invalid file range: \
+         offset {offset:#x}, size {size:#x}"
+    )]
+    SegmentOutOfBounds {
+        index: usize,
+        offset: usize,
+        size: usize,
+    },
+    /// The file size of a segment differs from its memory size, see
+    /// [`ParseOptions::allow_size_mismatch`](crate::ParseOptions::allow_size_mismatch).
+    #[error(
+        "parse error: segment {} file size ({file_size:#x}) differs from memory size \
+         ({memory_size:#x})",
+        segment.0
+    )]
+    SegmentSizeMismatch {
+        segment: SegmentId,
+        file_size: usize,
+        memory_size: usize,
+    },
+    /// The memory range of a segment exceeds the address space.
+    #[error("parse error: segment memory range overflows: {start:#x} + {size:#x}")]
+    SegmentRangeOverflow { start: usize, size: usize },
+    /// The configured note alignment is neither 4 nor 8.
+    #[error("parse error: invalid note alignment: {align}")]
+    InvalidNoteAlignment { align: usize },
+    /// A required note is missing, see
+    /// [`ParseOptions::allow_missing_notes`](crate::ParseOptions::allow_missing_notes).
+    #[error("parse error: missing note: {name}")]
+    MissingNote { name: &'static str },
+    /// The note at the given file offset is `size` bytes long, but only
+    /// `available` bytes are left in its segment or section.
+    #[error(
+        "parse error: note at file offset {offset:#x}: note size ({size:#x}) exceeds \
+         remaining segment data ({available:#x})"
+    )]
+    NoteTooLarge {
+        offset: usize,
+        size: usize,
+        available: usize,
+    },
+    /// The note at the given file offset could not be parsed. `note`
+    /// describes the note, e.g. `NT_FILE note`.
+    #[error("parse error: {note} at file offset {offset:#x}: {reason}")]
+    InvalidNote {
+        note: &'static str,
+        offset: usize,
+        reason: String,
+    },
+    /// The notes of the thread with the given index could not be parsed.
+    /// `offset` is the file offset of the thread's `NT_PRSTATUS` note.
+    #[error("parse error: thread {thread} (note at file offset {offset:#x}): {reason}")]
+    InvalidThreadNote {
+        thread: usize,
+        offset: usize,
+        reason: String,
+    },
+    /// The core is compressed with a format whose feature is disabled.
+    #[error("parse error: {format}-compressed core, but the `{format}` feature is disabled")]
+    CompressionUnsupported { format: &'static str },
+    /// Decompressing the core failed.
+    #[error("parse error: {format} decompression failed: {reason}")]
+    Decompression {
+        format: &'static str,
+        reason: String,
+    },
+    /// The decompressed core exceeds
+    /// [`ParseOptions::max_decompressed_size`](crate::ParseOptions::max_decompressed_size).
+    #[error("parse error: decompressed core exceeds the maximum size of {limit} bytes")]
+    DecompressedSizeExceeded { limit: u64 },
+}
+
+impl ParseError {
+    /// Return the error message, without the "parse error" prefix.
+    pub(crate) fn into_message(self) -> String {
+        let message = self.to_string();
+        match message.strip_prefix("parse error: ") {
+            Some(rest) => rest.to_string(),
+            None => message,
+        }
     }
 }
 
-impl std::error::Error for ParseError {}
+/// A non-fatal problem encountered while parsing a core file.
+///
+/// Warnings describe oddities that parsing tolerated, see
+/// [`Core::warnings`](crate::Core::warnings).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// The padding after the name or descriptor of the note at the given
+    /// file offset is not zeroed.
+    ///
+    /// This suggests that the note was written with a different alignment
+    /// than the one used for parsing (see
+    /// [`ParseOptions::note_alignment`](crate::ParseOptions::note_alignment)),
+    /// in which case this and the following notes are likely garbage.
+    NotePadding { offset: usize },
+    /// The file size of a segment differs from its memory size, see
+    /// [`ParseOptions::allow_size_mismatch`](crate::ParseOptions::allow_size_mismatch).
+    SegmentSizeMismatch {
+        segment: SegmentId,
+        file_size: usize,
+        memory_size: usize,
+    },
+    /// A segment extends beyond the end of the file and only `present` of its
+    /// `file_size` bytes could be read, see
+    /// [`ParseOptions::tolerate_truncation`](crate::ParseOptions::tolerate_truncation).
+    TruncatedSegment {
+        segment: SegmentId,
+        file_size: usize,
+        present: usize,
+    },
+    /// The `NT_FILE` note contains fewer paths than mappings, so the last
+    /// `count` file mappings have an empty path.
+    MissingNtFilePaths { count: usize },
+    /// The memory ranges of two segments overlap.
+    OverlappingSegments { first: SegmentId, second: SegmentId },
+    /// A `PT_LOAD` segment has a non-zero physical address that differs from
+    /// its virtual address, which may indicate a non-standard dumper.
+    DivergentPhysicalAddress {
+        segment: SegmentId,
+        virtual_address: usize,
+        physical_address: usize,
+    },
+    /// The section header table could not be parsed and was ignored, so
+    /// notes in `SHT_NOTE` sections are missing.
+    InvalidSectionHeaders { reason: String },
+    /// The `SHT_NOTE` section at the given file offset could not be parsed
+    /// and was ignored.
+    InvalidNoteSection { offset: usize, reason: String },
+}
 
-impl From<String> for ParseError {
-    fn from(s: String) -> Self {
-        Self(s)
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotePadding { offset } => {
+                write!(f, "note at file offset {offset:#x} has non-zero padding")
+            }
+            Self::SegmentSizeMismatch {
+                segment,
+                file_size,
+                memory_size,
+            } => write!(
+                f,
+                "segment {} file size ({file_size:#x}) differs from memory size ({memory_size:#x})",
+                segment.0,
+            ),
+            Self::TruncatedSegment {
+                segment,
+                file_size,
+                present,
+            } => write!(
+                f,
+                "segment {} is truncated: {present:#x} of {file_size:#x} bytes present",
+                segment.0,
+            ),
+            Self::MissingNtFilePaths { count } => {
+                write!(f, "NT_FILE note is missing {count} paths")
+            }
+            Self::OverlappingSegments { first, second } => {
+                write!(f, "segments {} and {} overlap", first.0, second.0)
+            }
+            Self::DivergentPhysicalAddress {
+                segment,
+                virtual_address,
+                physical_address,
+            } => write!(
+                f,
+                "segment {} has physical address {physical_address:#x}, \
+                 which differs from its virtual address {virtual_address:#x}",
+                segment.0
+            ),
+            Self::InvalidSectionHeaders { reason } => {
+                write!(f, "ignoring invalid section header table: {reason}")
+            }
+            Self::InvalidNoteSection { offset, reason } => write!(
+                f,
+                "ignoring invalid note section at file offset {offset:#x}: {reason}"
+            ),
+        }
     }
 }
@@ -0,0 +1,53 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::core::Core;
+
+/// The location of a segment's bytes in a memory dump written by
+/// [`Core::write_memory_dump`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SegmentIndexEntry {
+    pub vm_start: usize,
+    /// Offset of the segment's bytes in the dump.
+    pub file_offset: u64,
+    /// Number of bytes written for the segment.
+    pub len: usize,
+}
+
+impl fmt::Debug for SegmentIndexEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SegmentIndexEntry")
+            .field("vm_start", &format_args!("{:#x}", self.vm_start))
+            .field("file_offset", &format_args!("{:#x}", self.file_offset))
+            .field("len", &format_args!("{:#x}", self.len))
+            .finish()
+    }
+}
+
+impl<'d> Core<'d> {
+    /// Write the data of all segments to `out`, one after the other, in
+    /// ascending address order.
+    ///
+    /// Returns an index mapping the segments' virtual addresses to offsets
+    /// in the written output. Only bytes present in the core are written,
+    /// so segments whose contents were not dumped take up less space than
+    /// they span in memory.
+    pub fn write_memory_dump<W: Write>(&self, mut out: W) -> io::Result<Vec<SegmentIndexEntry>> {
+        let mut segments: Vec<_> = self.segments.iter().collect();
+        segments.sort_by_key(|s| s.vm_start);
+
+        let mut index = Vec::with_capacity(segments.len());
+        let mut offset = 0;
+        for segment in segments {
+            out.write_all(segment.data)?;
+            index.push(SegmentIndexEntry {
+                vm_start: segment.vm_start,
+                file_offset: offset,
+                len: segment.data.len(),
+            });
+            offset += segment.data.len() as u64;
+        }
+
+        Ok(index)
+    }
+}
@@ -1,11 +1,32 @@
 #![deny(missing_debug_implementations)]
 /* TODO #![deny(missing_docs)] */
 
+mod address_space;
+mod arch;
+mod args;
+mod buffer;
+mod cet;
+mod compression;
 mod core;
 mod ctypes;
+mod diff;
 mod elf;
 mod error;
+mod export;
+mod link_map;
+mod module;
+pub mod note;
+mod options;
+mod owned;
+pub mod prelude;
 mod read;
+mod registers;
+mod scan;
+pub mod signal;
+mod stats;
+mod summary;
+mod symbol;
+mod triage;
 mod util;
 
 const _FORCE_64BIT: () = assert!(
@@ -13,5 +34,26 @@ const _FORCE_64BIT: () = assert!(
     "this library only supports 64-bit targets"
 );
 
-pub use crate::core::{Core, FileMapping, ProcessInfo, Registers, Segment, ThreadInfo};
-pub use crate::error::ParseError;
+pub use crate::address_space::{AddressRegion, RegionKind};
+pub use crate::arch::{Architecture, Endianness, WordSize};
+pub use crate::buffer::CoreBuffer;
+pub use crate::cet::CetFeatures;
+pub use crate::compression::{decompress, decompress_with_options};
+pub use crate::core::{
+    Core, CoreSummary, FileMapping, ProcessInfo, Registers, Segment, SegmentFlags, SegmentId,
+    ThreadId, ThreadInfo, MAX_RECONSTRUCTED_FILE_SIZE,
+};
+pub use crate::diff::{CoreDiff, RegisterChange, ThreadDiff};
+pub use crate::error::{ParseError, ParseWarning};
+pub use crate::export::SegmentIndexEntry;
+pub use crate::link_map::LinkMapEntry;
+pub use crate::module::ElfHeader;
+pub use crate::options::ParseOptions;
+pub use crate::owned::{
+    OwnedCore, OwnedFileMapping, OwnedProcessInfo, OwnedSegment, OwnedThreadInfo,
+};
+pub use crate::registers::RegisterName;
+pub use crate::stats::CoreStats;
+pub use crate::summary::{CrashSummary, ModuleSummary, ThreadSummary};
+pub use crate::symbol::Symbol;
+pub use crate::triage::{MappingMatch, RipStatus, TlsSanity};
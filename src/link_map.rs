@@ -0,0 +1,129 @@
+use std::fmt;
+
+use crate::core::Core;
+use crate::ctypes::{AT_PHDR, AT_PHENT, AT_PHNUM, DT_DEBUG, DT_NULL, PT_DYNAMIC, PT_PHDR};
+use crate::read::ReadExt;
+
+/// Upper bound on the number of `link_map` entries followed, to guard
+/// against cyclic lists in corrupt memory.
+const MAX_LINK_MAP_ENTRIES: usize = 4096;
+
+/// Size of an `Elf64_Phdr`, the only program header entry size supported.
+const PHDR_SIZE: usize = 56;
+
+/// Upper bound on the number of program headers read from memory. ELF
+/// headers store the count in 16 bits, so larger `AT_PHNUM` values are
+/// corrupt.
+const MAX_PHNUM: usize = 0xffff;
+
+/// A shared object loaded by the dynamic linker, as recorded in its
+/// `link_map` list.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LinkMapEntry<'d> {
+    /// Address of the `link_map` structure itself.
+    pub address: usize,
+    /// Difference between the addresses in the object's ELF file and its
+    /// addresses in memory (`l_addr`).
+    pub load_bias: usize,
+    /// Path of the object (`l_name`). Empty for the main executable.
+    pub name: &'d [u8],
+    /// Address of the object's dynamic section (`l_ld`).
+    pub dynamic: usize,
+}
+
+impl fmt::Debug for LinkMapEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkMapEntry")
+            .field("address", &format_args!("{:#x}", self.address))
+            .field("load_bias", &format_args!("{:#x}", self.load_bias))
+            .field("name", &String::from_utf8_lossy(self.name))
+            .field("dynamic", &format_args!("{:#x}", self.dynamic))
+            .finish()
+    }
+}
+
+impl<'d> Core<'d> {
+    /// Walk the dynamic linker's list of loaded shared objects.
+    ///
+    /// The list is found through the `DT_DEBUG` entry of the executable's
+    /// dynamic section, which the dynamic linker points to its `r_debug`
+    /// structure. The executable's dynamic section in turn is located
+    /// through the `AT_PHDR` entry of the `NT_AUXV` note.
+    ///
+    /// Unlike the file map, this reports the load bias of each object, and
+    /// also works if the `NT_FILE` note is missing. Returns `None` if any of
+    /// the required memory is not present in the core, or if the executable
+    /// is statically linked.
+    pub fn walk_link_map(&self) -> Option<Vec<LinkMapEntry<'d>>> {
+        let r_debug = self.find_r_debug()?;
+
+        // struct r_debug { int r_version; struct link_map *r_map; ... }
+        let mut addr = self.read_u64_at(r_debug.checked_add(8)?)? as usize;
+        let mut entries = Vec::new();
+        while addr != 0 {
+            if entries.len() == MAX_LINK_MAP_ENTRIES {
+                return None;
+            }
+
+            // struct link_map { l_addr, l_name, l_ld, l_next, l_prev }
+            let field = |offset: usize| Some(self.read_u64_at(addr.checked_add(offset)?)? as usize);
+            entries.push(LinkMapEntry {
+                address: addr,
+                load_bias: field(0)?,
+                name: self.read_c_string_at(field(8)?, usize::MAX)?,
+                dynamic: field(16)?,
+            });
+            addr = field(24)?;
+        }
+
+        Some(entries)
+    }
+
+    /// Return the address of the dynamic linker's `r_debug` structure.
+    fn find_r_debug(&self) -> Option<usize> {
+        let order = self.byte_order();
+        let phdr = self.auxv_value(AT_PHDR)? as usize;
+        let phent = self.auxv_value(AT_PHENT)? as usize;
+        let phnum = (self.auxv_value(AT_PHNUM)? as usize).min(MAX_PHNUM);
+        if phent != PHDR_SIZE {
+            return None;
+        }
+
+        // Return the `(p_type, p_vaddr)` of the program header at `index`.
+        let read_ph = |index: usize| -> Option<(u32, usize)> {
+            let addr = phdr.checked_add(index.checked_mul(phent)?)?;
+            let mut data = self.read_memory(addr, 24)?;
+            let type_ = data.read_u32_with(order).ok()?;
+            let _flags = data.read_u32_with(order).ok()?;
+            let _offset = data.read_u64_with(order).ok()?;
+            let vaddr = data.read_u64_with(order).ok()? as usize;
+            Some((type_, vaddr))
+        };
+
+        let mut phdr_vaddr = None;
+        let mut dynamic_vaddr = None;
+        // A corrupt `AT_PHNUM` may exceed the table, so stop at the first
+        // program header that cannot be read.
+        for index in 0..phnum {
+            let Some(ph) = read_ph(index) else { break };
+            match ph {
+                (PT_PHDR, vaddr) => phdr_vaddr = Some(vaddr),
+                (PT_DYNAMIC, vaddr) => dynamic_vaddr = Some(vaddr),
+                _ => {}
+            }
+        }
+
+        // Executables without a `PT_PHDR` are not position-independent.
+        let load_bias = phdr.wrapping_sub(phdr_vaddr.unwrap_or(phdr));
+        let mut addr = load_bias.wrapping_add(dynamic_vaddr?);
+        loop {
+            let tag = self.read_u64_at(addr)?;
+            let value = self.read_u64_at(addr.checked_add(8)?)?;
+            match tag {
+                DT_NULL => return None,
+                DT_DEBUG if value != 0 => return Some(value as usize),
+                _ => addr = addr.checked_add(16)?,
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use structview::View;
+
+use crate::core::Core;
+use crate::ctypes::{Elf64_Ehdr, ELFCLASS64, ELFDATA2LSB, ELFDATA2MSB};
+use crate::read::{with_byte_order, ByteOrder};
+
+/// The ELF header of a module mapped into the crashed process.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElfHeader {
+    /// The OS/ABI byte of `e_ident`.
+    pub os_abi: u8,
+    /// The ABI version byte of `e_ident`.
+    pub abi_version: u8,
+    /// The object file type, e.g. `ET_DYN` (3) for shared objects.
+    pub type_: u16,
+    pub machine: u16,
+    pub version: u32,
+    pub entry: u64,
+    pub ph_offset: u64,
+    pub sh_offset: u64,
+    pub flags: u32,
+    pub header_size: u16,
+    pub ph_entry_size: u16,
+    pub ph_count: u16,
+    pub sh_entry_size: u16,
+    pub sh_count: u16,
+    pub sh_string_index: u16,
+}
+
+impl fmt::Debug for ElfHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ElfHeader")
+            .field("os_abi", &self.os_abi)
+            .field("abi_version", &self.abi_version)
+            .field("type_", &self.type_)
+            .field("machine", &self.machine)
+            .field("version", &self.version)
+            .field("entry", &format_args!("{:#x}", self.entry))
+            .field("ph_offset", &format_args!("{:#x}", self.ph_offset))
+            .field("sh_offset", &format_args!("{:#x}", self.sh_offset))
+            .field("flags", &format_args!("{:#x}", self.flags))
+            .field("header_size", &self.header_size)
+            .field("ph_entry_size", &self.ph_entry_size)
+            .field("ph_count", &self.ph_count)
+            .field("sh_entry_size", &self.sh_entry_size)
+            .field("sh_count", &self.sh_count)
+            .field("sh_string_index", &self.sh_string_index)
+            .finish()
+    }
+}
+
+impl<BO: byteorder::ByteOrder> From<&Elf64_Ehdr<BO>> for ElfHeader {
+    fn from(ehdr: &Elf64_Ehdr<BO>) -> Self {
+        Self {
+            os_abi: ehdr.e_ident[7],
+            abi_version: ehdr.e_ident[8],
+            type_: ehdr.e_type.to_int(),
+            machine: ehdr.e_machine.to_int(),
+            version: ehdr.e_version.to_int(),
+            entry: ehdr.e_entry.to_int(),
+            ph_offset: ehdr.e_phoff.to_int(),
+            sh_offset: ehdr.e_shoff.to_int(),
+            flags: ehdr.e_flags.to_int(),
+            header_size: ehdr.e_ehsize.to_int(),
+            ph_entry_size: ehdr.e_phentsize.to_int(),
+            ph_count: ehdr.e_phnum.to_int(),
+            sh_entry_size: ehdr.e_shentsize.to_int(),
+            sh_count: ehdr.e_shnum.to_int(),
+            sh_string_index: ehdr.e_shstrndx.to_int(),
+        }
+    }
+}
+
+impl Core<'_> {
+    /// Return the ELF header of the module mapped from the given path, as
+    /// found in the memory of the crashed process.
+    ///
+    /// The header is read from the start of the module's mapping at file
+    /// offset zero. The kernel usually dumps this page even for unmodified
+    /// file mappings. Returns `None` if the module is not mapped, its first
+    /// page is not present in the core, or it does not start with a 64-bit
+    /// ELF header.
+    pub fn module_elf_header(&self, path: &[u8]) -> Option<ElfHeader> {
+        let base = self
+            .file_map
+            .iter()
+            .filter(|m| m.file_path == path && m.file_offset == 0)
+            .min_by_key(|m| m.vm_start)?;
+
+        let data = self.read_memory(base.vm_start, 64)?;
+        if !data.starts_with(b"\x7fELF") || data[4] != ELFCLASS64 {
+            return None;
+        }
+
+        let order = match data[5] {
+            ELFDATA2LSB => ByteOrder::Little,
+            ELFDATA2MSB => ByteOrder::Big,
+            _ => return None,
+        };
+        with_byte_order!(order, BO => {
+            Elf64_Ehdr::<BO>::view(data).ok().map(Into::into)
+        })
+    }
+}
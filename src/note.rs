@@ -0,0 +1,213 @@
+//! Known ELF note owner names and types.
+//!
+//! The meaning of a note type depends on the note's owner name, so both have
+//! to be compared when identifying a note. For example, type `3` is
+//! `NT_PRPSINFO` for `CORE` notes but `NT_GNU_BUILD_ID` for `GNU` notes.
+
+use std::any::Any;
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+pub use crate::ctypes::constants::{
+    NT_386_IOPERM, NT_AUXV, NT_CMDLINE, NT_FILE, NT_GDB_TDESC, NT_GNU_BUILD_ID,
+    NT_GNU_PROPERTY_TYPE_0, NT_PRFPREG, NT_PRPSINFO, NT_PRSTATUS, NT_SIGINFO, NT_X86_XSTATE,
+};
+
+/// Owner name of the standard core file notes.
+pub const CORE: &[u8] = b"CORE";
+/// Owner name of Linux-specific notes, like extended register sets.
+pub const LINUX: &[u8] = b"LINUX";
+/// Owner name of GNU notes.
+pub const GNU: &[u8] = b"GNU";
+/// Owner name of notes added by GDB's `gcore`.
+pub const GDB: &[u8] = b"GDB";
+
+/// Known notes, as `(owner name, type, label)` triples.
+const KNOWN_NOTES: &[(&[u8], u32, &str)] = &[
+    (CORE, NT_PRSTATUS, "NT_PRSTATUS"),
+    (CORE, NT_PRFPREG, "NT_PRFPREG"),
+    (CORE, NT_PRPSINFO, "NT_PRPSINFO"),
+    (CORE, NT_AUXV, "NT_AUXV"),
+    (CORE, NT_SIGINFO, "NT_SIGINFO"),
+    (CORE, NT_FILE, "NT_FILE"),
+    (CORE, NT_CMDLINE, "NT_CMDLINE"),
+    (LINUX, NT_386_IOPERM, "NT_386_IOPERM"),
+    (LINUX, NT_X86_XSTATE, "NT_X86_XSTATE"),
+    (GNU, NT_GNU_BUILD_ID, "NT_GNU_BUILD_ID"),
+    (GNU, NT_GNU_PROPERTY_TYPE_0, "NT_GNU_PROPERTY_TYPE_0"),
+    (GDB, NT_GDB_TDESC, "NT_GDB_TDESC"),
+];
+
+/// Return a readable label for the note with the given owner name and type.
+///
+/// Returns `None` if the note is not known to this crate.
+pub fn note_type_name(name: &[u8], type_: u32) -> Option<&'static str> {
+    KNOWN_NOTES
+        .iter()
+        .find(|(n, t, _)| *n == name && *t == type_)
+        .map(|(_, _, label)| *label)
+}
+
+/// The number and total size of the notes with a given owner name and type.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NoteSummary<'d> {
+    pub name: &'d [u8],
+    pub type_: u32,
+    /// Number of notes with this owner name and type.
+    pub count: usize,
+    /// Sum of the descriptor sizes of these notes, in bytes.
+    pub total_bytes: usize,
+}
+
+impl fmt::Debug for NoteSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoteSummary")
+            .field("name", &String::from_utf8_lossy(self.name))
+            .field("type_", &format_args!("{:#x}", self.type_))
+            .field("count", &self.count)
+            .field("total_bytes", &self.total_bytes)
+            .finish()
+    }
+}
+
+/// A note of a core file, without any interpretation of its contents.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawNote<'d> {
+    /// The owner name, without its NUL terminator. Empty for notes without
+    /// an owner name, i.e. with an `n_namesz` of zero.
+    pub name: &'d [u8],
+    pub type_: u32,
+    pub desc: &'d [u8],
+    /// File offset of the note header.
+    pub offset: usize,
+}
+
+impl fmt::Debug for RawNote<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawNote")
+            .field("name", &String::from_utf8_lossy(self.name))
+            .field("type_", &format_args!("{:#x}", self.type_))
+            .field("desc", &format_args!("[{} bytes]", self.desc.len()))
+            .field("offset", &format_args!("{:#x}", self.offset))
+            .finish()
+    }
+}
+
+type Decoder = Box<dyn Fn(&[u8]) -> Arc<dyn Any + Send + Sync> + Send + Sync>;
+
+/// A set of decoders for notes not interpreted by this crate.
+///
+/// Decoders are registered for an owner name and type, and are run by
+/// [`Core::parse_with_registry`](crate::Core::parse_with_registry) on the
+/// descriptors of matching notes. Decoders registered for notes this crate
+/// interprets itself, like `NT_PRSTATUS`, are never run.
+#[derive(Default)]
+pub struct NoteRegistry {
+    /// Decoders by note type and owner name. Keying by type first allows
+    /// looking up owner names as `&[u8]`, without allocating.
+    decoders: HashMap<u32, HashMap<Vec<u8>, Decoder>>,
+}
+
+impl NoteRegistry {
+    /// Create a registry without any decoders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a decoder for the notes with the given owner name and type.
+    ///
+    /// A decoder registered earlier for the same name and type is replaced.
+    pub fn register<T, F>(&mut self, name: &[u8], type_: u32, decoder: F)
+    where
+        T: Any + Send + Sync,
+        F: Fn(&[u8]) -> T + Send + Sync + 'static,
+    {
+        let decoder: Decoder = Box::new(move |desc| Arc::new(decoder(desc)));
+        self.decoders
+            .entry(type_)
+            .or_default()
+            .insert(name.to_vec(), decoder);
+    }
+
+    /// Run the matching decoder on the given note, if one is registered.
+    pub(crate) fn decode<'d>(&self, note: &RawNote<'d>) -> Option<CustomNote<'d>> {
+        let decoder = self.decoders.get(&note.type_)?.get(note.name)?;
+        Some(CustomNote {
+            note: note.clone(),
+            value: decoder(note.desc),
+        })
+    }
+}
+
+impl fmt::Debug for NoteRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<_> = self
+            .decoders
+            .iter()
+            .flat_map(|(type_, names)| names.keys().map(move |name| (name, type_)))
+            .collect();
+        keys.sort();
+        f.debug_set()
+            .entries(
+                keys.into_iter()
+                    .map(|(name, type_)| format!("{}/{type_:#x}", String::from_utf8_lossy(name))),
+            )
+            .finish()
+    }
+}
+
+/// A note decoded by a decoder of a [`NoteRegistry`].
+///
+/// Custom notes are compared, ordered, and hashed by their `note` only, as
+/// the decoded values are opaque.
+#[derive(Clone)]
+pub struct CustomNote<'d> {
+    pub note: RawNote<'d>,
+    /// The value returned by the decoder.
+    pub value: Arc<dyn Any + Send + Sync>,
+}
+
+impl PartialEq for CustomNote<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.note == other.note
+    }
+}
+
+impl Eq for CustomNote<'_> {}
+
+impl Ord for CustomNote<'_> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.note.cmp(&other.note)
+    }
+}
+
+impl PartialOrd for CustomNote<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for CustomNote<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.note.hash(state);
+    }
+}
+
+impl CustomNote<'_> {
+    /// Return the decoded value, if it has type `T`.
+    pub fn value<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
+
+impl fmt::Debug for CustomNote<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomNote")
+            .field("note", &self.note)
+            .field("value", &format_args!("…"))
+            .finish()
+    }
+}
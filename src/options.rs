@@ -0,0 +1,103 @@
+use crate::ctypes::NT_CMDLINE;
+
+/// Options controlling how strictly a core file is validated during parsing.
+///
+/// The [`Default`] options are strict, and are what [`Core::parse`] uses.
+///
+/// [`Core::parse`]: crate::Core::parse
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    /// Accept `PT_LOAD` segments whose file size differs from their memory
+    /// size.
+    ///
+    /// The kernel omits the contents of some mappings from cores, so this is
+    /// required to parse most cores it produces. The data of such segments
+    /// is shorter than the memory they span. Each such segment is reported
+    /// as a [`ParseWarning::SegmentSizeMismatch`]. Default: `false`.
+    ///
+    /// [`ParseWarning::SegmentSizeMismatch`]: crate::ParseWarning::SegmentSizeMismatch
+    pub allow_size_mismatch: bool,
+    /// Accept cores that lack the `NT_PRPSINFO` or `NT_FILE` notes.
+    ///
+    /// A missing `NT_PRPSINFO` yields a zeroed [`ProcessInfo`], a missing
+    /// `NT_FILE` an empty file map. Default: `false`.
+    ///
+    /// [`ProcessInfo`]: crate::ProcessInfo
+    pub allow_missing_notes: bool,
+    /// Accept `PT_LOAD` segments that extend beyond the end of the file, as
+    /// found in truncated cores.
+    ///
+    /// The data of such segments contains only the bytes present in the
+    /// file. Each such segment is reported as a
+    /// [`ParseWarning::TruncatedSegment`]. Default: `false`.
+    ///
+    /// [`ParseWarning::TruncatedSegment`]: crate::ParseWarning::TruncatedSegment
+    pub tolerate_truncation: bool,
+    /// Verify that segment addresses are aligned to the segment alignment.
+    /// Default: `true`.
+    pub verify_alignment: bool,
+    /// Look for notes at the start of `PT_LOAD` segments if the `PT_NOTE`
+    /// segments don't contain an `NT_PRPSINFO` note.
+    ///
+    /// Some non-conformant dumpers store their notes in a `PT_LOAD` segment.
+    /// Segments are only considered if they start with a `CORE` or `LINUX`
+    /// note, but ordinary memory might still be misinterpreted as notes.
+    /// Default: `false`.
+    pub find_notes_in_load_segments: bool,
+    /// Alignment of note names and descriptors, in bytes. Must be 4 or 8.
+    ///
+    /// If `None`, the alignment is 8 for `PT_NOTE` segments with a `p_align`
+    /// of 8, as written by some toolchains, and 4 otherwise, as written by
+    /// the kernel. Default: `None`.
+    pub note_alignment: Option<usize>,
+    /// Drop notes that are identical to a note in an earlier `PT_NOTE`
+    /// segment or note section, as written by some dumpers that emit notes
+    /// twice.
+    ///
+    /// Notes are identical if their owner name, type and descriptor are
+    /// equal. Identical notes within the same segment are kept, as they may
+    /// belong to different threads. Default: `false`.
+    pub dedup_notes: bool,
+    /// Note type of the `CORE` note that holds the full command line, as
+    /// used by [`Core::full_command_line`]. Default: [`NT_CMDLINE`].
+    ///
+    /// [`Core::full_command_line`]: crate::Core::full_command_line
+    /// [`NT_CMDLINE`]: crate::note::NT_CMDLINE
+    pub cmdline_note_type: u32,
+    /// Note type of the `CORE` or `LINUX` note that holds the x86 debug
+    /// registers DR0 to DR7 of a thread, as used by
+    /// [`ThreadInfo::debug_registers`].
+    ///
+    /// Linux doesn't write debug registers to cores, so there is no standard
+    /// note type. Some dumpers add them in a custom note following the
+    /// thread's `NT_PRSTATUS` note, as eight 64-bit values. If `None`, debug
+    /// registers are not extracted. Default: `None`.
+    ///
+    /// [`ThreadInfo::debug_registers`]: crate::ThreadInfo::debug_registers
+    pub debug_registers_note_type: Option<u32>,
+    /// Maximum size of a decompressed core, in bytes, as used by
+    /// [`decompress_with_options`].
+    ///
+    /// Decompression fails once its output exceeds this size, so a small
+    /// compressed file cannot exhaust memory. Default: 64 GiB.
+    ///
+    /// [`decompress_with_options`]: crate::decompress_with_options
+    pub max_decompressed_size: u64,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            allow_size_mismatch: false,
+            allow_missing_notes: false,
+            tolerate_truncation: false,
+            verify_alignment: true,
+            find_notes_in_load_segments: false,
+            note_alignment: None,
+            dedup_notes: false,
+            cmdline_note_type: NT_CMDLINE,
+            debug_registers_note_type: None,
+            max_decompressed_size: 64 << 30,
+        }
+    }
+}
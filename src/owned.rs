@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::{
+    Core, FileMapping, ProcessInfo, Registers, SegmentFlags, SegmentId, ThreadId, ThreadInfo,
+};
+use crate::signal::{SignalInfo, SignalSet};
+
+/// A summary of a [`Core`] that does not borrow from the core file data.
+///
+/// Segment bytes are not retained, only the address ranges of the segments.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedCore {
+    pub segments: Vec<OwnedSegment>,
+    pub process: OwnedProcessInfo,
+    pub threads: Vec<OwnedThreadInfo>,
+    pub file_map: Vec<OwnedFileMapping>,
+    pub signal: Option<SignalInfo>,
+}
+
+impl Core<'_> {
+    /// Detach the core metadata from the backing buffer.
+    ///
+    /// This copies all metadata into owned buffers and drops the segment
+    /// data, so the returned [`OwnedCore`] does not retain any segment bytes.
+    pub fn into_owned(self) -> OwnedCore {
+        OwnedCore {
+            segments: self
+                .segments
+                .iter()
+                .map(|s| OwnedSegment {
+                    id: s.id,
+                    vm_start: s.vm_start,
+                    vm_end: s.vm_end,
+                    physical_address: s.physical_address,
+                    flags: s.flags,
+                    align: s.align,
+                })
+                .collect(),
+            process: (&self.process).into(),
+            threads: self.threads.iter().map(Into::into).collect(),
+            file_map: intern_file_map(&self.file_map),
+            signal: self.signal,
+        }
+    }
+}
+
+/// Convert the given file mappings, storing each distinct path only once.
+fn intern_file_map(file_map: &[FileMapping<'_>]) -> Vec<OwnedFileMapping> {
+    let mut paths: HashMap<&[u8], Arc<[u8]>> = HashMap::new();
+    file_map
+        .iter()
+        .map(|m| OwnedFileMapping {
+            vm_start: m.vm_start,
+            vm_end: m.vm_end,
+            file_offset: m.file_offset,
+            file_path: paths
+                .entry(m.file_path)
+                .or_insert_with(|| m.file_path.into())
+                .clone(),
+            flags: m.flags,
+        })
+        .collect()
+}
+
+impl OwnedCore {
+    /// Return the distinct paths of the mapped files, in order of their first
+    /// appearance in the file map.
+    pub fn file_paths(&self) -> Vec<&[u8]> {
+        let mut paths: Vec<&[u8]> = Vec::new();
+        for mapping in &self.file_map {
+            if !paths.contains(&&*mapping.file_path) {
+                paths.push(&mapping.file_path);
+            }
+        }
+        paths
+    }
+}
+
+/// The address range and flags of a segment, without its data.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedSegment {
+    pub id: SegmentId,
+    pub vm_start: usize,
+    pub vm_end: usize,
+    pub physical_address: usize,
+    pub flags: SegmentFlags,
+    pub align: usize,
+}
+
+impl fmt::Debug for OwnedSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSegment")
+            .field("id", &self.id)
+            .field("vm_start", &format_args!("{:#x}", self.vm_start))
+            .field("vm_end", &format_args!("{:#x}", self.vm_end))
+            .field(
+                "physical_address",
+                &format_args!("{:#x}", self.physical_address),
+            )
+            .field("flags", &self.flags)
+            .field("align", &format_args!("{:#x}", self.align))
+            .finish()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedProcessInfo {
+    pub state: i8,
+    pub state_name: char,
+    pub zombie: bool,
+    pub nice: i8,
+    pub flags: u64,
+    pub uid: i32,
+    pub gid: i32,
+    pub pid: i32,
+    pub ppid: i32,
+    pub pgrp: i32,
+    pub sid: i32,
+    pub file_name: Vec<u8>,
+    pub command: Vec<u8>,
+    current_signal: Option<u32>,
+}
+
+impl fmt::Debug for OwnedProcessInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedProcessInfo")
+            .field("state", &self.state)
+            .field("state_name", &self.state_name)
+            .field("zombie", &self.zombie)
+            .field("nice", &self.nice)
+            .field("flags", &format_args!("{:#x}", self.flags))
+            .field("uid", &self.uid)
+            .field("gid", &self.gid)
+            .field("pid", &self.pid)
+            .field("ppid", &self.ppid)
+            .field("pgrp", &self.pgrp)
+            .field("sid", &self.sid)
+            .field("file_name", &String::from_utf8_lossy(&self.file_name))
+            .field("command", &String::from_utf8_lossy(&self.command))
+            .field("current_signal", &self.current_signal)
+            .finish()
+    }
+}
+
+impl OwnedProcessInfo {
+    /// Return the signal that caused the core dump, if any.
+    ///
+    /// See [`ProcessInfo::current_signal`].
+    pub fn current_signal(&self) -> Option<u32> {
+        self.current_signal
+    }
+}
+
+impl From<&ProcessInfo<'_>> for OwnedProcessInfo {
+    fn from(info: &ProcessInfo<'_>) -> Self {
+        Self {
+            state: info.state,
+            state_name: info.state_name,
+            zombie: info.zombie,
+            nice: info.nice,
+            flags: info.flags,
+            uid: info.uid,
+            gid: info.gid,
+            pid: info.pid,
+            ppid: info.ppid,
+            pgrp: info.pgrp,
+            sid: info.sid,
+            file_name: info.file_name.to_vec(),
+            command: info.command.to_vec(),
+            current_signal: info.current_signal(),
+        }
+    }
+}
+
+/// A [`ThreadInfo`] that does not borrow from the core file data.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedThreadInfo {
+    pub pid: i32,
+    pub registers: Registers,
+    pub fp_valid: bool,
+    pub user_time: Duration,
+    pub system_time: Duration,
+    pub current_signal: Option<u32>,
+    pub pending_signals: SignalSet,
+    pub blocked_signals: SignalSet,
+    prstatus: Vec<u8>,
+    io_permission_bitmap: Option<Vec<u8>>,
+    debug_registers: Option<[u64; 8]>,
+}
+
+impl fmt::Debug for OwnedThreadInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedThreadInfo")
+            .field("pid", &self.pid)
+            .field("registers", &self.registers)
+            .field("fp_valid", &self.fp_valid)
+            .field("user_time", &self.user_time)
+            .field("system_time", &self.system_time)
+            .field("current_signal", &self.current_signal)
+            .field("pending_signals", &self.pending_signals)
+            .field("blocked_signals", &self.blocked_signals)
+            .field("prstatus", &format_args!("…"))
+            .field(
+                "io_permission_bitmap",
+                &self
+                    .io_permission_bitmap
+                    .as_ref()
+                    .map(|_| format_args!("…")),
+            )
+            .field("debug_registers", &self.debug_registers)
+            .finish()
+    }
+}
+
+impl OwnedThreadInfo {
+    /// Return the identity of this thread.
+    pub fn id(&self) -> ThreadId {
+        ThreadId(self.pid)
+    }
+
+    /// Return the raw `NT_PRSTATUS` note descriptor of this thread.
+    ///
+    /// See [`ThreadInfo::raw_prstatus`].
+    pub fn raw_prstatus(&self) -> &[u8] {
+        &self.prstatus
+    }
+
+    /// Return the I/O permission bitmap of this thread.
+    ///
+    /// See [`ThreadInfo::io_permission_bitmap`].
+    pub fn io_permission_bitmap(&self) -> Option<&[u8]> {
+        self.io_permission_bitmap.as_deref()
+    }
+
+    /// Return the debug registers DR0 to DR7 of this thread.
+    ///
+    /// See [`ThreadInfo::debug_registers`].
+    pub fn debug_registers(&self) -> Option<[u64; 8]> {
+        self.debug_registers
+    }
+}
+
+impl From<&ThreadInfo<'_>> for OwnedThreadInfo {
+    fn from(info: &ThreadInfo<'_>) -> Self {
+        Self {
+            pid: info.pid,
+            registers: info.registers.clone(),
+            fp_valid: info.fp_valid,
+            user_time: info.user_time,
+            system_time: info.system_time,
+            current_signal: info.current_signal,
+            pending_signals: info.pending_signals,
+            blocked_signals: info.blocked_signals,
+            prstatus: info.raw_prstatus().to_vec(),
+            io_permission_bitmap: info.io_permission_bitmap().map(<[u8]>::to_vec),
+            debug_registers: info.debug_registers(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedFileMapping {
+    pub vm_start: usize,
+    pub vm_end: usize,
+    pub file_offset: u64,
+    /// Path of the mapped file. Mappings of the same file converted by
+    /// [`Core::into_owned`] share the path buffer.
+    pub file_path: Arc<[u8]>,
+    pub flags: Option<SegmentFlags>,
+}
+
+impl fmt::Debug for OwnedFileMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedFileMapping")
+            .field("vm_start", &format_args!("{:#x}", self.vm_start))
+            .field("vm_end", &format_args!("{:#x}", self.vm_end))
+            .field("file_offset", &format_args!("{:#x}", self.file_offset))
+            .field("file_path", &String::from_utf8_lossy(&self.file_path))
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+impl From<&FileMapping<'_>> for OwnedFileMapping {
+    fn from(mapping: &FileMapping<'_>) -> Self {
+        Self {
+            vm_start: mapping.vm_start,
+            vm_end: mapping.vm_end,
+            file_offset: mapping.file_offset,
+            file_path: mapping.file_path.into(),
+            flags: mapping.flags,
+        }
+    }
+}
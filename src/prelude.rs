@@ -0,0 +1,12 @@
+//! Commonly used types, for glob importing with `use licore::prelude::*`.
+//!
+//! The prelude contains [`Core`] and the types describing its contents, the
+//! owned [`OwnedCore`] variant, [`SignalInfo`], and [`ParseError`]. Less
+//! commonly used types, like those returned by the analysis helpers, remain
+//! available at the crate root.
+
+pub use crate::signal::SignalInfo;
+pub use crate::{
+    Core, CoreSummary, FileMapping, OwnedCore, ParseError, ParseOptions, ProcessInfo, RegisterName,
+    Registers, Segment, SegmentFlags, SegmentId, ThreadId, ThreadInfo,
+};
@@ -1,16 +1,46 @@
 pub(crate) type Result<T> = std::result::Result<T, &'static str>;
 
+/// The byte order of multi-byte values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum ByteOrder {
+    Little,
+    Big,
+}
+
+/// Evaluate `$body` with `$bo` bound to the `byteorder` type matching the
+/// given [`ByteOrder`].
+///
+/// This bridges between the byte order detected at runtime and the
+/// `structview` types, which encode the byte order in the type.
+macro_rules! with_byte_order {
+    ($order:expr, $bo:ident => $body:expr) => {
+        match $order {
+            $crate::read::ByteOrder::Little => {
+                type $bo = byteorder::LE;
+                $body
+            }
+            $crate::read::ByteOrder::Big => {
+                type $bo = byteorder::BE;
+                $body
+            }
+        }
+    };
+}
+
+pub(crate) use with_byte_order;
+
+/// Extension trait for reading values from byte slices.
+///
+/// The plain `read_*` methods read little-endian values. The `read_*_with`
+/// methods take the byte order as a parameter.
 pub(crate) trait ReadExt<'d> {
     fn read_slice(&mut self, n: usize) -> Result<&'d [u8]>;
     fn read_array<const N: usize>(&mut self) -> Result<&'d [u8; N]>;
-    fn read_u8(&mut self) -> Result<u8>;
-    fn read_i8(&mut self) -> Result<i8>;
-    fn read_u16(&mut self) -> Result<u16>;
-    fn read_i16(&mut self) -> Result<i16>;
+    fn read_owned_array<const N: usize>(&mut self) -> Result<[u8; N]>;
     fn read_u32(&mut self) -> Result<u32>;
-    fn read_i32(&mut self) -> Result<i32>;
     fn read_u64(&mut self) -> Result<u64>;
-    fn read_i64(&mut self) -> Result<i64>;
+    fn read_u32_with(&mut self, order: ByteOrder) -> Result<u32>;
+    fn read_u64_with(&mut self, order: ByteOrder) -> Result<u64>;
 }
 
 impl<'d> ReadExt<'d> for &'d [u8] {
@@ -28,35 +58,29 @@ impl<'d> ReadExt<'d> for &'d [u8] {
         self.read_slice(N).map(|s| s.try_into().unwrap())
     }
 
-    fn read_u8(&mut self) -> Result<u8> {
-        self.read_array().map(|b| u8::from_le_bytes(*b))
-    }
-
-    fn read_i8(&mut self) -> Result<i8> {
-        self.read_array().map(|b| i8::from_le_bytes(*b))
-    }
-
-    fn read_u16(&mut self) -> Result<u16> {
-        self.read_array().map(|b| u16::from_le_bytes(*b))
-    }
-
-    fn read_i16(&mut self) -> Result<i16> {
-        self.read_array().map(|b| i16::from_le_bytes(*b))
+    fn read_owned_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        self.read_array().copied()
     }
 
     fn read_u32(&mut self) -> Result<u32> {
-        self.read_array().map(|b| u32::from_le_bytes(*b))
+        self.read_u32_with(ByteOrder::Little)
     }
 
-    fn read_i32(&mut self) -> Result<i32> {
-        self.read_array().map(|b| i32::from_le_bytes(*b))
+    fn read_u64(&mut self) -> Result<u64> {
+        self.read_u64_with(ByteOrder::Little)
     }
 
-    fn read_u64(&mut self) -> Result<u64> {
-        self.read_array().map(|b| u64::from_le_bytes(*b))
+    fn read_u32_with(&mut self, order: ByteOrder) -> Result<u32> {
+        self.read_owned_array().map(|b| match order {
+            ByteOrder::Little => u32::from_le_bytes(b),
+            ByteOrder::Big => u32::from_be_bytes(b),
+        })
     }
 
-    fn read_i64(&mut self) -> Result<i64> {
-        self.read_array().map(|b| i64::from_le_bytes(*b))
+    fn read_u64_with(&mut self, order: ByteOrder) -> Result<u64> {
+        self.read_owned_array().map(|b| match order {
+            ByteOrder::Little => u64::from_le_bytes(b),
+            ByteOrder::Big => u64::from_be_bytes(b),
+        })
     }
 }
@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::core::Registers;
+
+/// The name of a register in [`Registers`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RegisterName {
+    Rax,
+    Rbx,
+    Rcx,
+    Rdx,
+    Rbp,
+    Rsp,
+    Rsi,
+    Rdi,
+    OrigRax,
+    R8,
+    R9,
+    R10,
+    R11,
+    R12,
+    R13,
+    R14,
+    R15,
+    Rip,
+    Rflags,
+    Cs,
+    Ds,
+    Ss,
+    Es,
+    Fs,
+    Gs,
+    FsBase,
+    GsBase,
+}
+
+impl RegisterName {
+    /// All registers, in the order of the fields of [`Registers`].
+    pub const ALL: [RegisterName; 27] = [
+        Self::Rax,
+        Self::Rbx,
+        Self::Rcx,
+        Self::Rdx,
+        Self::Rbp,
+        Self::Rsp,
+        Self::Rsi,
+        Self::Rdi,
+        Self::OrigRax,
+        Self::R8,
+        Self::R9,
+        Self::R10,
+        Self::R11,
+        Self::R12,
+        Self::R13,
+        Self::R14,
+        Self::R15,
+        Self::Rip,
+        Self::Rflags,
+        Self::Cs,
+        Self::Ds,
+        Self::Ss,
+        Self::Es,
+        Self::Fs,
+        Self::Gs,
+        Self::FsBase,
+        Self::GsBase,
+    ];
+
+    /// Return the lowercase name of the register, e.g. `"rip"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rax => "rax",
+            Self::Rbx => "rbx",
+            Self::Rcx => "rcx",
+            Self::Rdx => "rdx",
+            Self::Rbp => "rbp",
+            Self::Rsp => "rsp",
+            Self::Rsi => "rsi",
+            Self::Rdi => "rdi",
+            Self::OrigRax => "orig_rax",
+            Self::R8 => "r8",
+            Self::R9 => "r9",
+            Self::R10 => "r10",
+            Self::R11 => "r11",
+            Self::R12 => "r12",
+            Self::R13 => "r13",
+            Self::R14 => "r14",
+            Self::R15 => "r15",
+            Self::Rip => "rip",
+            Self::Rflags => "rflags",
+            Self::Cs => "cs",
+            Self::Ds => "ds",
+            Self::Ss => "ss",
+            Self::Es => "es",
+            Self::Fs => "fs",
+            Self::Gs => "gs",
+            Self::FsBase => "fs_base",
+            Self::GsBase => "gs_base",
+        }
+    }
+}
+
+impl fmt::Display for RegisterName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Registers {
+    /// Return the registers in the order the kernel stores them in
+    /// `elf_gregset_t` and `struct user_regs_struct`.
+    pub(crate) fn gregset(&self) -> [u64; 27] {
+        let r = self;
+        [
+            r.r15, r.r14, r.r13, r.r12, r.rbp, r.rbx, r.r11, r.r10, r.r9, r.r8, r.rax, r.rcx,
+            r.rdx, r.rsi, r.rdi, r.orig_rax, r.rip, r.cs, r.rflags, r.rsp, r.ss, r.fs_base,
+            r.gs_base, r.ds, r.es, r.fs, r.gs,
+        ]
+    }
+
+    /// Serialize the registers into the little-endian byte layout of the
+    /// kernel's `struct user_regs_struct`.
+    ///
+    /// This is the layout of `elf_gregset_t` in `NT_PRSTATUS` notes and of
+    /// the buffer filled by `ptrace(PTRACE_GETREGS)`.
+    pub fn to_user_regs_bytes(&self) -> [u8; 216] {
+        let mut bytes = [0; 216];
+        for (chunk, value) in bytes.chunks_exact_mut(8).zip(self.gregset()) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Return the value of the given register.
+    pub fn get(&self, name: RegisterName) -> u64 {
+        match name {
+            RegisterName::Rax => self.rax,
+            RegisterName::Rbx => self.rbx,
+            RegisterName::Rcx => self.rcx,
+            RegisterName::Rdx => self.rdx,
+            RegisterName::Rbp => self.rbp,
+            RegisterName::Rsp => self.rsp,
+            RegisterName::Rsi => self.rsi,
+            RegisterName::Rdi => self.rdi,
+            RegisterName::OrigRax => self.orig_rax,
+            RegisterName::R8 => self.r8,
+            RegisterName::R9 => self.r9,
+            RegisterName::R10 => self.r10,
+            RegisterName::R11 => self.r11,
+            RegisterName::R12 => self.r12,
+            RegisterName::R13 => self.r13,
+            RegisterName::R14 => self.r14,
+            RegisterName::R15 => self.r15,
+            RegisterName::Rip => self.rip,
+            RegisterName::Rflags => self.rflags,
+            RegisterName::Cs => self.cs,
+            RegisterName::Ds => self.ds,
+            RegisterName::Ss => self.ss,
+            RegisterName::Es => self.es,
+            RegisterName::Fs => self.fs,
+            RegisterName::Gs => self.gs,
+            RegisterName::FsBase => self.fs_base,
+            RegisterName::GsBase => self.gs_base,
+        }
+    }
+
+    /// Return an iterator over all registers and their values, in the order
+    /// of [`RegisterName::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = (RegisterName, u64)> + '_ {
+        RegisterName::ALL
+            .into_iter()
+            .map(|name| (name, self.get(name)))
+    }
+
+    /// Return the registers whose values differ between `self` and `other`,
+    /// as `(name, old, new)` triples.
+    pub fn diff(&self, other: &Registers) -> Vec<(RegisterName, u64, u64)> {
+        self.iter()
+            .zip(other.iter())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((name, old), (_, new))| (name, old, new))
+            .collect()
+    }
+}
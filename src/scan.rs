@@ -0,0 +1,63 @@
+use crate::core::{Core, Segment, ThreadInfo};
+use crate::read::ReadExt;
+
+/// Maximum number of stack bytes scanned by [`Core::scan_return_addresses`].
+const RETURN_ADDRESS_SCAN_LIMIT: usize = 64 * 1024;
+
+impl<'d> Core<'d> {
+    /// Scan a segment for values that look like pointers into mapped memory.
+    ///
+    /// Every 8-byte aligned slot of the segment is interpreted as a pointer.
+    /// For each slot whose value falls into a segment of this core, the
+    /// iterator yields `(address_of_slot, target_address)`.
+    pub fn scan_pointers<'a>(
+        &'a self,
+        segment: &'a Segment<'d>,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let order = self.byte_order();
+        let skip = segment.vm_start.wrapping_neg() % 8;
+        let data = segment.data.get(skip..).unwrap_or_default();
+
+        data.chunks_exact(8)
+            .enumerate()
+            .filter_map(move |(i, mut slot)| {
+                let target = slot.read_u64_with(order).ok()? as usize;
+                self.segment_at(target)?;
+
+                let addr = segment.vm_start + skip + i * 8;
+                Some((addr, target))
+            })
+    }
+
+    /// Scan the stack of the given thread for values that look like return
+    /// addresses.
+    ///
+    /// Starting at the stack pointer, every 8-byte aligned slot of the stack
+    /// segment is interpreted as a pointer, and values that point into an
+    /// executable segment are returned in stack order, innermost first. At
+    /// most 64 KiB of stack are scanned.
+    ///
+    /// This is a fallback for unwinding code without frame pointers or
+    /// unwind information. The result contains false positives, such as
+    /// stale return addresses of returned calls and function pointers stored
+    /// in local variables, so it should be treated as a hint only.
+    pub fn scan_return_addresses(&self, thread: &ThreadInfo) -> Vec<u64> {
+        let order = self.byte_order();
+        let sp = thread.registers.stack_pointer() as usize;
+        let Some(stack) = self.segment_at(sp) else {
+            return Vec::new();
+        };
+
+        let start = (sp - stack.vm_start).next_multiple_of(8);
+        let data = stack.data.get(start..).unwrap_or_default();
+        let data = &data[..data.len().min(RETURN_ADDRESS_SCAN_LIMIT)];
+
+        data.chunks_exact(8)
+            .filter_map(|mut slot| slot.read_u64_with(order).ok())
+            .filter(|&value| {
+                self.segment_at(value as usize)
+                    .is_some_and(|s| s.flags.is_executable())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,309 @@
+//! Linux signal numbers and names.
+//!
+//! Signal numbers follow the Linux x86-64 convention. In particular, signal 16
+//! is `SIGSTKFLT` (not `SIGEMT`, as on some other architectures), and the
+//! real-time signals range from 32 (`SIGRTMIN`) to 64 (`SIGRTMAX`).
+//!
+//! Note that these are the kernel's real-time signal numbers. glibc reserves
+//! the first two real-time signals for internal use, so the `SIGRTMIN` seen
+//! by C programs is usually 34.
+
+use std::fmt;
+
+use byteorder::ByteOrder;
+
+use crate::ctypes::siginfo_t;
+
+pub const SIGHUP: u32 = 1;
+pub const SIGINT: u32 = 2;
+pub const SIGQUIT: u32 = 3;
+pub const SIGILL: u32 = 4;
+pub const SIGTRAP: u32 = 5;
+pub const SIGABRT: u32 = 6;
+pub const SIGBUS: u32 = 7;
+pub const SIGFPE: u32 = 8;
+pub const SIGKILL: u32 = 9;
+pub const SIGUSR1: u32 = 10;
+pub const SIGSEGV: u32 = 11;
+pub const SIGUSR2: u32 = 12;
+pub const SIGPIPE: u32 = 13;
+pub const SIGALRM: u32 = 14;
+pub const SIGTERM: u32 = 15;
+pub const SIGSTKFLT: u32 = 16;
+pub const SIGCHLD: u32 = 17;
+pub const SIGCONT: u32 = 18;
+pub const SIGSTOP: u32 = 19;
+pub const SIGTSTP: u32 = 20;
+pub const SIGTTIN: u32 = 21;
+pub const SIGTTOU: u32 = 22;
+pub const SIGURG: u32 = 23;
+pub const SIGXCPU: u32 = 24;
+pub const SIGXFSZ: u32 = 25;
+pub const SIGVTALRM: u32 = 26;
+pub const SIGPROF: u32 = 27;
+pub const SIGWINCH: u32 = 28;
+pub const SIGIO: u32 = 29;
+pub const SIGPWR: u32 = 30;
+pub const SIGSYS: u32 = 31;
+pub const SIGRTMIN: u32 = 32;
+pub const SIGRTMAX: u32 = 64;
+
+/// Signal names, indexed by signal number minus one.
+const NAMES: [&str; 64] = [
+    "SIGHUP",
+    "SIGINT",
+    "SIGQUIT",
+    "SIGILL",
+    "SIGTRAP",
+    "SIGABRT",
+    "SIGBUS",
+    "SIGFPE",
+    "SIGKILL",
+    "SIGUSR1",
+    "SIGSEGV",
+    "SIGUSR2",
+    "SIGPIPE",
+    "SIGALRM",
+    "SIGTERM",
+    "SIGSTKFLT",
+    "SIGCHLD",
+    "SIGCONT",
+    "SIGSTOP",
+    "SIGTSTP",
+    "SIGTTIN",
+    "SIGTTOU",
+    "SIGURG",
+    "SIGXCPU",
+    "SIGXFSZ",
+    "SIGVTALRM",
+    "SIGPROF",
+    "SIGWINCH",
+    "SIGIO",
+    "SIGPWR",
+    "SIGSYS",
+    "SIGRTMIN",
+    "SIGRTMIN+1",
+    "SIGRTMIN+2",
+    "SIGRTMIN+3",
+    "SIGRTMIN+4",
+    "SIGRTMIN+5",
+    "SIGRTMIN+6",
+    "SIGRTMIN+7",
+    "SIGRTMIN+8",
+    "SIGRTMIN+9",
+    "SIGRTMIN+10",
+    "SIGRTMIN+11",
+    "SIGRTMIN+12",
+    "SIGRTMIN+13",
+    "SIGRTMIN+14",
+    "SIGRTMIN+15",
+    "SIGRTMAX-16",
+    "SIGRTMAX-15",
+    "SIGRTMAX-14",
+    "SIGRTMAX-13",
+    "SIGRTMAX-12",
+    "SIGRTMAX-11",
+    "SIGRTMAX-10",
+    "SIGRTMAX-9",
+    "SIGRTMAX-8",
+    "SIGRTMAX-7",
+    "SIGRTMAX-6",
+    "SIGRTMAX-5",
+    "SIGRTMAX-4",
+    "SIGRTMAX-3",
+    "SIGRTMAX-2",
+    "SIGRTMAX-1",
+    "SIGRTMAX",
+];
+
+/// Return the name of the given signal number, e.g. `"SIGSEGV"` for 11.
+///
+/// Returns `None` if `n` is not a valid signal number.
+pub fn signal_name(n: u32) -> Option<&'static str> {
+    let idx = n.checked_sub(1)?;
+    NAMES.get(idx as usize).copied()
+}
+
+/// Return the signal numbers contained in the given signal mask.
+///
+/// Signal `n` is represented by bit `n - 1` of the mask, as in the kernel's
+/// `sigset_t`.
+pub fn signals_in_mask(mask: u64) -> impl Iterator<Item = u32> {
+    (1..=64).filter(move |n| mask & (1 << (n - 1)) != 0)
+}
+
+/// A set of signals, as stored in the kernel's `sigset_t`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignalSet(u64);
+
+impl SignalSet {
+    /// Create a signal set from a kernel signal mask, in which signal `n` is
+    /// represented by bit `n - 1`.
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Return whether the set contains the given signal.
+    pub fn contains(&self, signal: u32) -> bool {
+        (1..=64).contains(&signal) && self.0 & (1 << (signal - 1)) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return the signal numbers in this set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u32> {
+        signals_in_mask(self.0)
+    }
+}
+
+impl fmt::Debug for SignalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|n| signal_name(n).unwrap_or("?")))
+            .finish()
+    }
+}
+
+/// The signal state of a thread.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignalState {
+    /// The signal currently being delivered, if any.
+    pub current: Option<u32>,
+    /// Signals that are pending delivery.
+    pub pending: SignalSet,
+    /// Signals that are blocked.
+    pub blocked: SignalSet,
+}
+
+impl fmt::Debug for SignalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalState")
+            .field(
+                "current",
+                &self.current.map(|n| signal_name(n).unwrap_or("?")),
+            )
+            .field("pending", &self.pending)
+            .field("blocked", &self.blocked)
+            .finish()
+    }
+}
+
+/// Information about the signal that caused the core dump, from the
+/// `NT_SIGINFO` note.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignalInfo {
+    pub signo: i32,
+    pub errno: i32,
+    pub code: i32,
+    /// The faulting address, for kernel-generated signals that report one
+    /// (`SIGSEGV`, `SIGBUS`, `SIGILL`, `SIGFPE`, `SIGTRAP`).
+    pub fault_address: Option<usize>,
+}
+
+impl SignalInfo {
+    /// Return the name of the signal, e.g. `"SIGSEGV"`.
+    pub fn name(&self) -> Option<&'static str> {
+        u32::try_from(self.signo).ok().and_then(signal_name)
+    }
+
+    /// Return a human-readable description of the signal's cause, based on
+    /// `si_code`.
+    ///
+    /// For unknown signal/code combinations a generic description is
+    /// returned.
+    pub fn description(&self) -> &'static str {
+        let specific = match (self.signo as u32, self.code) {
+            (SIGSEGV, 1) => Some("address not mapped to object"),
+            (SIGSEGV, 2) => Some("invalid permissions for mapped object"),
+            (SIGSEGV, 3) => Some("failed address bound checks"),
+            (SIGSEGV, 4) => Some("failed protection key checks"),
+            (SIGBUS, 1) => Some("invalid address alignment"),
+            (SIGBUS, 2) => Some("nonexistent physical address"),
+            (SIGBUS, 3) => Some("object-specific hardware error"),
+            (SIGBUS, 4) => Some("hardware memory error consumed on a machine check"),
+            (SIGBUS, 5) => Some("hardware memory error detected but not consumed"),
+            (SIGILL, 1) => Some("illegal opcode"),
+            (SIGILL, 2) => Some("illegal operand"),
+            (SIGILL, 3) => Some("illegal addressing mode"),
+            (SIGILL, 4) => Some("illegal trap"),
+            (SIGILL, 5) => Some("privileged opcode"),
+            (SIGILL, 6) => Some("privileged register"),
+            (SIGILL, 7) => Some("coprocessor error"),
+            (SIGILL, 8) => Some("internal stack error"),
+            (SIGFPE, 1) => Some("integer divide by zero"),
+            (SIGFPE, 2) => Some("integer overflow"),
+            (SIGFPE, 3) => Some("floating-point divide by zero"),
+            (SIGFPE, 4) => Some("floating-point overflow"),
+            (SIGFPE, 5) => Some("floating-point underflow"),
+            (SIGFPE, 6) => Some("floating-point inexact result"),
+            (SIGFPE, 7) => Some("floating-point invalid operation"),
+            (SIGFPE, 8) => Some("subscript out of range"),
+            (SIGTRAP, 1) => Some("process breakpoint"),
+            (SIGTRAP, 2) => Some("process trace trap"),
+            (SIGTRAP, 3) => Some("process taken branch trap"),
+            (SIGTRAP, 4) => Some("hardware breakpoint/watchpoint"),
+            _ => None,
+        };
+        if let Some(desc) = specific {
+            return desc;
+        }
+
+        // Codes that are valid for every signal.
+        match self.code {
+            0 => "sent by kill",
+            0x80 => "sent by the kernel",
+            -1 => "sent by sigqueue",
+            -2 => "POSIX timer expired",
+            -3 => "POSIX message queue state changed",
+            -4 => "AIO completed",
+            -5 => "queued SIGIO",
+            -6 => "sent by tkill",
+            _ => "unknown cause",
+        }
+    }
+}
+
+impl fmt::Debug for SignalInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalInfo")
+            .field("signo", &self.signo)
+            .field("errno", &self.errno)
+            .field("code", &self.code)
+            .field(
+                "fault_address",
+                &self.fault_address.map(|a| format!("{a:#x}")),
+            )
+            .finish()
+    }
+}
+
+impl<BO: ByteOrder> From<&siginfo_t<BO>> for SignalInfo {
+    fn from(siginfo: &siginfo_t<BO>) -> Self {
+        let signo = siginfo.si_signo.to_int();
+        let code = siginfo.si_code.to_int();
+
+        // Only kernel-generated fault signals (positive `si_code`) carry a
+        // faulting address. Signals sent by other processes store the
+        // sender's pid and uid in the same place.
+        let fault_address = match signo as u32 {
+            SIGSEGV | SIGBUS | SIGILL | SIGFPE | SIGTRAP if code > 0 => {
+                let addr = BO::read_u64(&siginfo.sifields[..8]);
+                Some(addr as usize)
+            }
+            _ => None,
+        };
+
+        Self {
+            signo,
+            errno: siginfo.si_errno.to_int(),
+            code,
+            fault_address,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::core::Core;
+
+/// Counts and sizes describing a core, as returned by [`Core::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoreStats {
+    pub thread_count: usize,
+    pub segment_count: usize,
+    pub mapping_count: usize,
+    /// Number of segment bytes present in the core file, see
+    /// [`Core::total_file_backed_bytes`].
+    pub loaded_bytes: u64,
+    /// Number of bytes of virtual memory spanned by all segments, see
+    /// [`Core::total_mapped_bytes`].
+    pub mapped_bytes: u64,
+}
+
+impl Core<'_> {
+    /// Return the number of threads of the crashed process.
+    pub fn thread_count(&self) -> usize {
+        self.threads.len()
+    }
+
+    /// Return the number of loadable segments of this core.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Return the number of file mappings of the crashed process.
+    pub fn mapping_count(&self) -> usize {
+        self.file_map.len()
+    }
+
+    /// Return the counts and sizes describing this core.
+    pub fn stats(&self) -> CoreStats {
+        CoreStats {
+            thread_count: self.thread_count(),
+            segment_count: self.segment_count(),
+            mapping_count: self.mapping_count(),
+            loaded_bytes: self.total_file_backed_bytes(),
+            mapped_bytes: self.total_mapped_bytes(),
+        }
+    }
+}
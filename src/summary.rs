@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::core::Core;
+use crate::signal::signal_name;
+
+/// A normalized summary of a crash, containing only information that is
+/// commonly available across crash dump formats.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CrashSummary<'d> {
+    pub pid: i32,
+    /// The signal that caused the crash, if known.
+    pub signal: Option<u32>,
+    pub modules: Vec<ModuleSummary<'d>>,
+    pub threads: Vec<ThreadSummary>,
+}
+
+/// A file mapped into the crashed process.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModuleSummary<'d> {
+    pub path: &'d [u8],
+    /// Start address of the lowest mapping of the file.
+    pub vm_start: usize,
+    /// End address of the highest mapping of the file.
+    pub vm_end: usize,
+}
+
+impl fmt::Debug for ModuleSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModuleSummary")
+            .field("path", &String::from_utf8_lossy(self.path))
+            .field("vm_start", &format_args!("{:#x}", self.vm_start))
+            .field("vm_end", &format_args!("{:#x}", self.vm_end))
+            .finish()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadSummary {
+    pub pid: i32,
+    pub instruction_pointer: u64,
+    pub stack_pointer: u64,
+}
+
+impl fmt::Debug for ThreadSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadSummary")
+            .field("pid", &self.pid)
+            .field(
+                "instruction_pointer",
+                &format_args!("{:#018x}", self.instruction_pointer),
+            )
+            .field(
+                "stack_pointer",
+                &format_args!("{:#018x}", self.stack_pointer),
+            )
+            .finish()
+    }
+}
+
+impl<'d> Core<'d> {
+    /// Return a normalized summary of this core.
+    ///
+    /// Modules are listed in order of their first appearance in the file map.
+    pub fn summary(&self) -> CrashSummary<'d> {
+        let mut modules: Vec<ModuleSummary<'d>> = Vec::new();
+        for mapping in &self.file_map {
+            match modules.iter_mut().find(|m| m.path == mapping.file_path) {
+                Some(module) => {
+                    module.vm_start = module.vm_start.min(mapping.vm_start);
+                    module.vm_end = module.vm_end.max(mapping.vm_end);
+                }
+                None => modules.push(ModuleSummary {
+                    path: mapping.file_path,
+                    vm_start: mapping.vm_start,
+                    vm_end: mapping.vm_end,
+                }),
+            }
+        }
+
+        let threads = self
+            .threads
+            .iter()
+            .map(|t| ThreadSummary {
+                pid: t.pid,
+                instruction_pointer: t.registers.instruction_pointer(),
+                stack_pointer: t.registers.stack_pointer(),
+            })
+            .collect();
+
+        CrashSummary {
+            pid: self.process.pid,
+            signal: self.process.current_signal(),
+            modules,
+            threads,
+        }
+    }
+
+    /// Return a one-line, human-readable description of the crash.
+    ///
+    /// The description names the process, the signal that caused the dump,
+    /// and the instruction pointer of the thread that received it, e.g.
+    /// `PID 1234 (myapp) crashed with SIGSEGV at 0x401136 on thread 1235 (4
+    /// threads, 37 mappings)`.
+    pub fn describe(&self) -> String {
+        let name = self.process.file_name_str();
+        let mut s = format!("PID {} ({name})", self.process.pid);
+
+        let signo = self
+            .signal
+            .and_then(|s| u32::try_from(s.signo).ok())
+            .or(self.process.current_signal());
+        match signo {
+            Some(n) => match signal_name(n) {
+                Some(sig) => s += &format!(" crashed with {sig}"),
+                None => s += &format!(" crashed with signal {n}"),
+            },
+            None => s += " dumped core",
+        }
+
+        // Fall back to the first thread, as the kernel writes the notes of
+        // the dumping thread first.
+        let thread = self
+            .threads
+            .iter()
+            .find(|t| signo.is_some() && t.current_signal == signo)
+            .or(self.threads.first());
+        if let Some(thread) = thread {
+            let rip = thread.registers.instruction_pointer();
+            s += &format!(" at {rip:#x} on thread {}", thread.pid);
+        }
+
+        s += &format!(
+            " ({} threads, {} mappings)",
+            self.threads.len(),
+            self.file_map.len()
+        );
+        s
+    }
+}
@@ -0,0 +1,31 @@
+use crate::core::Core;
+
+/// A symbol resolved by a user-supplied resolver, see [`Core::symbolize_with`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol {
+    pub name: String,
+    /// Offset of the address from the start of the symbol.
+    pub offset: u64,
+}
+
+impl Core<'_> {
+    /// Resolve the symbol at the given virtual address using the given
+    /// resolver.
+    ///
+    /// The address is translated to the file mapped at it and the offset
+    /// within that file, which are then passed to `resolve`. The resolver is
+    /// responsible for looking up the symbol in the file's symbol table.
+    ///
+    /// Returns `None` if no file is mapped at the address or the resolver
+    /// finds no symbol.
+    pub fn symbolize_with<F>(&self, addr: usize, resolve: F) -> Option<Symbol>
+    where
+        F: FnOnce(&[u8], u64) -> Option<Symbol>,
+    {
+        let mapping = self.file_map.iter().find(|m| m.contains(addr))?;
+        let file_offset = mapping
+            .file_offset
+            .checked_add((addr - mapping.vm_start) as u64)?;
+        resolve(mapping.file_path, file_offset)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::core::{Core, FileMapping, Registers, ThreadInfo};
+use crate::signal::SIGSEGV;
+
+/// Size of the gap the kernel keeps below a growing stack by default
+/// (`stack_guard_gap`), in bytes.
+const STACK_GUARD_GAP: usize = 256 * 4096;
+
+/// Classification of a thread's instruction pointer by the memory it points
+/// into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RipStatus {
+    /// `rip` points into an executable, non-writable segment, as expected.
+    Executable,
+    /// `rip` points into a writable segment. This is suspicious, as it
+    /// suggests code executing from the stack or heap.
+    Writable,
+    /// `rip` points into a segment that is neither executable nor writable.
+    NotExecutable,
+    /// `rip` does not point into any segment, e.g. after a jump through a
+    /// bad pointer.
+    Unmapped,
+}
+
+/// Result of comparing a file mapping against a file on disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MappingMatch {
+    /// All compared bytes are equal.
+    Match,
+    /// Some compared bytes differ, so the file on disk is not the one that
+    /// was mapped.
+    Mismatch,
+    /// None of the mapping's bytes are present in the core, so nothing could
+    /// be compared.
+    Unknown,
+}
+
+/// Anomalies in the TLS-related registers of a thread, as reported by
+/// [`Registers::tls_sanity`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TlsSanity {
+    /// The `fs` selector is non-zero.
+    pub fs_selector_set: bool,
+    /// The `gs` selector is non-zero.
+    pub gs_selector_set: bool,
+    /// `fs_base` does not point into any segment.
+    pub fs_base_unmapped: bool,
+}
+
+impl TlsSanity {
+    /// Return whether no anomalies were found.
+    pub fn is_ok(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Registers {
+    /// Check the TLS-related registers for anomalies.
+    ///
+    /// On x86-64 Linux, user space addresses thread-local storage through
+    /// `fs_base`, which points to the thread control block, while the `fs`
+    /// and `gs` selectors stay zero. Deviations from this usually indicate
+    /// corrupted registers, or a process that set up TLS in an unusual way.
+    /// `gs_base` is not checked, as user space usually leaves it unset.
+    pub fn tls_sanity(&self, core: &Core) -> TlsSanity {
+        TlsSanity {
+            fs_selector_set: self.fs != 0,
+            gs_selector_set: self.gs != 0,
+            fs_base_unmapped: core.segment_at(self.fs_base as usize).is_none(),
+        }
+    }
+}
+
+impl<'d> Core<'d> {
+    /// Classify the instruction pointer of the given thread by the
+    /// permissions of the segment it points into.
+    pub fn validate_rip(&self, thread: &ThreadInfo) -> RipStatus {
+        let rip = thread.registers.rip as usize;
+        let Some(segment) = self.segment_at(rip) else {
+            return RipStatus::Unmapped;
+        };
+
+        if segment.flags.is_writable() {
+            RipStatus::Writable
+        } else if segment.flags.is_executable() {
+            RipStatus::Executable
+        } else {
+            RipStatus::NotExecutable
+        }
+    }
+
+    /// Compare the bytes of a file mapping present in the core against the
+    /// given contents of the mapped file.
+    ///
+    /// Only bytes of non-writable segments that lie within `file_bytes` are
+    /// compared. The kernel only dumps file-backed pages that the process
+    /// modified, plus the first page of each mapped ELF file. Modified pages,
+    /// such as relocated RELRO pages, are expected to differ from the file,
+    /// so the mapping at file offset zero gives the most reliable result.
+    pub fn validate_mapping(&self, mapping: &FileMapping, file_bytes: &[u8]) -> MappingMatch {
+        let mut compared = false;
+        for segment in self.segments_in_range(mapping.vm_start..mapping.vm_end) {
+            if segment.flags.is_writable() {
+                continue;
+            }
+
+            // The part of the mapping whose bytes are present in the segment.
+            let start = segment.vm_start.max(mapping.vm_start);
+            let end = (segment.vm_start + segment.data.len()).min(mapping.vm_end);
+            if start >= end {
+                continue;
+            }
+
+            let Ok(file_start) = usize::try_from(mapping.file_offset) else {
+                continue;
+            };
+            let file_start = file_start.saturating_add(start - mapping.vm_start);
+            let Some(file_data) = file_bytes.get(file_start..) else {
+                continue;
+            };
+
+            let core_data = &segment.data[start - segment.vm_start..end - segment.vm_start];
+            let len = core_data.len().min(file_data.len());
+            if core_data[..len] != file_data[..len] {
+                return MappingMatch::Mismatch;
+            }
+            compared |= len > 0;
+        }
+
+        if compared {
+            MappingMatch::Match
+        } else {
+            MappingMatch::Unknown
+        }
+    }
+
+    /// Return whether the given thread most likely crashed because it
+    /// overflowed its stack.
+    ///
+    /// This heuristic assumes that the thread received the `SIGSEGV`
+    /// recorded in the core's signal info, and that its stack is the segment
+    /// containing its stack pointer, or the lowest segment above it if the
+    /// stack pointer already moved past the stack's end. The crash is
+    /// considered a stack overflow if the fault address lies below that
+    /// segment, within the kernel's default stack guard gap of 1 MiB. This
+    /// also covers thread stacks, whose guard pages are mapped below the
+    /// stack.
+    pub fn is_stack_overflow(&self, thread: &ThreadInfo) -> bool {
+        let Some(signal) = self.signal else {
+            return false;
+        };
+        let Some(fault_address) = signal.fault_address else {
+            return false;
+        };
+        if signal.signo != SIGSEGV as i32 || thread.current_signal != Some(SIGSEGV) {
+            return false;
+        }
+
+        let sp = thread.registers.stack_pointer() as usize;
+        let stack = self.segment_at(sp).or_else(|| {
+            self.segments
+                .iter()
+                .filter(|s| s.vm_start > sp && !s.is_empty())
+                .min_by_key(|s| s.vm_start)
+        });
+        let Some(stack) = stack else {
+            return false;
+        };
+
+        fault_address < stack.vm_start && stack.vm_start - fault_address <= STACK_GUARD_GAP
+    }
+}
@@ -0,0 +1,397 @@
+//! Assembly of minimal synthetic core files.
+//!
+//! [`CoreBuilder`] writes a little-endian x86-64 core consisting of the ELF
+//! header, the program header table, a `PT_NOTE` segment holding the
+//! `NT_PRSTATUS`, `NT_PRPSINFO` and `NT_FILE` notes, and the configured
+//! `PT_LOAD` segments. Tests tweak the builder, or patch the built bytes, to
+//! construct the fixtures they need.
+
+const EHDR_SIZE: usize = 64;
+const PHDR_SIZE: usize = 56;
+const SHDR_SIZE: usize = 64;
+
+/// File offset of `e_phnum` in the ELF header.
+pub const E_PHNUM_OFFSET: usize = 56;
+
+pub const PT_LOAD: u32 = 1;
+pub const PT_NOTE: u32 = 4;
+pub const PN_XNUM: u16 = 0xffff;
+
+pub const NT_PRSTATUS: u32 = 1;
+pub const NT_PRPSINFO: u32 = 3;
+pub const NT_AUXV: u32 = 6;
+pub const NT_FILE: u32 = 0x4649_4c45;
+
+pub const PF_W: u32 = 2;
+pub const PF_R: u32 = 4;
+
+pub const PAGE_SIZE: u64 = 0x1000;
+
+/// Indices of registers in `elf_gregset_t`.
+pub mod reg {
+    pub const RIP: usize = 16;
+    pub const RSP: usize = 19;
+}
+
+/// A thread, written as an `NT_PRSTATUS` note.
+#[derive(Clone, Debug)]
+pub struct Thread {
+    pub pid: i32,
+    /// The general purpose registers, in `elf_gregset_t` order.
+    pub gregs: [u64; 27],
+    pub cursig: i16,
+}
+
+/// A segment, written as a program header followed by its file data.
+#[derive(Clone, Debug)]
+pub struct Load {
+    pub type_: u32,
+    pub vaddr: u64,
+    pub paddr: u64,
+    pub memsz: u64,
+    pub flags: u32,
+    pub align: u64,
+    pub data: Vec<u8>,
+}
+
+impl Load {
+    /// A readable and writable `PT_LOAD` segment whose memory size matches
+    /// the size of its data.
+    pub fn new(vaddr: u64, data: Vec<u8>) -> Self {
+        Self {
+            type_: PT_LOAD,
+            vaddr,
+            paddr: 0,
+            memsz: data.len() as u64,
+            flags: PF_R | PF_W,
+            align: PAGE_SIZE,
+            data,
+        }
+    }
+}
+
+/// An `NT_FILE` entry.
+#[derive(Clone, Debug)]
+pub struct Mapping {
+    pub start: u64,
+    pub end: u64,
+    pub page_offset: u64,
+    pub path: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CoreBuilder {
+    pid: i32,
+    file_name: Vec<u8>,
+    command: Vec<u8>,
+    owner: Vec<u8>,
+    xnum: bool,
+    notes_as_load: bool,
+    osabi: u8,
+    threads: Vec<Thread>,
+    mappings: Vec<Mapping>,
+    note_segments: Vec<Vec<u8>>,
+    loads: Vec<Load>,
+}
+
+impl Default for CoreBuilder {
+    fn default() -> Self {
+        Self {
+            pid: 1000,
+            file_name: b"test".to_vec(),
+            command: b"./test ".to_vec(),
+            owner: b"CORE".to_vec(),
+            xnum: false,
+            notes_as_load: false,
+            osabi: 0,
+            threads: Vec::new(),
+            mappings: Vec::new(),
+            note_segments: Vec::new(),
+            loads: Vec::new(),
+        }
+    }
+}
+
+impl CoreBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pid(mut self, pid: i32) -> Self {
+        self.pid = pid;
+        self
+    }
+
+    pub fn command(mut self, command: &[u8]) -> Self {
+        self.command = command.to_vec();
+        self
+    }
+
+    /// Set the owner name of the standard notes.
+    pub fn owner(mut self, owner: &[u8]) -> Self {
+        self.owner = owner.to_vec();
+        self
+    }
+
+    /// Store the program header count in section header 0 and set `e_phnum`
+    /// to `PN_XNUM`, as the kernel does for cores with many segments.
+    pub fn xnum(mut self) -> Self {
+        self.xnum = true;
+        self
+    }
+
+    /// Write the main note segment as `PT_LOAD` instead of `PT_NOTE`, as
+    /// some non-conformant dumpers do.
+    pub fn notes_as_load(mut self) -> Self {
+        self.notes_as_load = true;
+        self
+    }
+
+    pub fn osabi(mut self, osabi: u8) -> Self {
+        self.osabi = osabi;
+        self
+    }
+
+    pub fn thread(self, pid: i32, gregs: [u64; 27]) -> Self {
+        self.thread_with(Thread {
+            pid,
+            gregs,
+            cursig: 0,
+        })
+    }
+
+    pub fn thread_with(mut self, thread: Thread) -> Self {
+        self.threads.push(thread);
+        self
+    }
+
+    pub fn mapping(mut self, start: u64, end: u64, page_offset: u64, path: &[u8]) -> Self {
+        self.mappings.push(Mapping {
+            start,
+            end,
+            page_offset,
+            path: path.to_vec(),
+        });
+        self
+    }
+
+    pub fn load(self, vaddr: u64, data: Vec<u8>) -> Self {
+        self.load_with(Load::new(vaddr, data))
+    }
+
+    pub fn load_with(mut self, load: Load) -> Self {
+        self.loads.push(load);
+        self
+    }
+
+    /// Add a `PT_NOTE` segment with the given raw contents, after the main
+    /// note segment.
+    pub fn note_segment(mut self, data: Vec<u8>) -> Self {
+        self.note_segments.push(data);
+        self
+    }
+
+    /// Return the encoded contents of the main note segment.
+    pub fn main_notes(&self) -> Vec<u8> {
+        let mut notes = Vec::new();
+        for thread in &self.threads {
+            let desc = encode_prstatus(thread);
+            notes.extend(encode_note(&self.owner, NT_PRSTATUS, &desc, 4));
+        }
+        let desc = self.encode_prpsinfo();
+        notes.extend(encode_note(&self.owner, NT_PRPSINFO, &desc, 4));
+        let desc = self.encode_file_map();
+        notes.extend(encode_note(&self.owner, NT_FILE, &desc, 4));
+        notes
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let note_segment = |data| Load {
+            type_: PT_NOTE,
+            vaddr: 0,
+            paddr: 0,
+            memsz: 0,
+            flags: 0,
+            align: 4,
+            data,
+        };
+
+        let mut main_notes = note_segment(self.main_notes());
+        if self.notes_as_load {
+            main_notes.type_ = PT_LOAD;
+            main_notes.memsz = main_notes.data.len() as u64;
+        }
+
+        let mut segments = vec![main_notes];
+        segments.extend(self.note_segments.iter().cloned().map(note_segment));
+        segments.extend(self.loads.iter().cloned());
+
+        let phnum = segments.len();
+        let mut offset = EHDR_SIZE + phnum * PHDR_SIZE;
+        let mut phdrs = Vec::new();
+        let mut contents = Vec::new();
+        for segment in &segments {
+            offset = offset.next_multiple_of(8);
+            contents.resize(offset - EHDR_SIZE - phnum * PHDR_SIZE, 0);
+            phdrs.extend(encode_phdr(segment, offset as u64));
+            contents.extend(&segment.data);
+            offset += segment.data.len();
+        }
+
+        let shoff = if self.xnum {
+            offset.next_multiple_of(8)
+        } else {
+            0
+        };
+
+        let mut data = self.encode_ehdr(phnum, shoff);
+        data.extend(phdrs);
+        data.extend(contents);
+        if self.xnum {
+            data.resize(shoff, 0);
+            data.extend(encode_xnum_shdr(phnum as u32));
+        }
+        data
+    }
+
+    fn encode_ehdr(&self, phnum: usize, shoff: usize) -> Vec<u8> {
+        let mut ehdr = Vec::with_capacity(EHDR_SIZE);
+        ehdr.extend(b"\x7fELF");
+        // ELFCLASS64, ELFDATA2LSB, EV_CURRENT.
+        ehdr.extend([2, 1, 1, self.osabi]);
+        ehdr.resize(16, 0);
+        put_u16(&mut ehdr, 4); // e_type: ET_CORE
+        put_u16(&mut ehdr, 62); // e_machine: EM_X86_64
+        put_u32(&mut ehdr, 1); // e_version
+        put_u64(&mut ehdr, 0); // e_entry
+        put_u64(&mut ehdr, EHDR_SIZE as u64); // e_phoff
+        put_u64(&mut ehdr, shoff as u64); // e_shoff
+        put_u32(&mut ehdr, 0); // e_flags
+        put_u16(&mut ehdr, EHDR_SIZE as u16); // e_ehsize
+        put_u16(&mut ehdr, PHDR_SIZE as u16); // e_phentsize
+        if self.xnum {
+            put_u16(&mut ehdr, PN_XNUM); // e_phnum
+            put_u16(&mut ehdr, SHDR_SIZE as u16); // e_shentsize
+            put_u16(&mut ehdr, 1); // e_shnum
+        } else {
+            put_u16(&mut ehdr, phnum as u16); // e_phnum
+            put_u16(&mut ehdr, 0); // e_shentsize
+            put_u16(&mut ehdr, 0); // e_shnum
+        }
+        put_u16(&mut ehdr, 0); // e_shstrndx
+        ehdr
+    }
+
+    fn encode_prpsinfo(&self) -> Vec<u8> {
+        let mut desc = Vec::with_capacity(136);
+        // pr_state, pr_sname, pr_zomb, pr_nice, padding.
+        desc.extend([0, b'R', 0, 0, 0, 0, 0, 0]);
+        put_u64(&mut desc, 0); // pr_flag
+        put_u32(&mut desc, 1000); // pr_uid
+        put_u32(&mut desc, 1000); // pr_gid
+        put_u32(&mut desc, self.pid as u32); // pr_pid
+        put_u32(&mut desc, 1); // pr_ppid
+        put_u32(&mut desc, self.pid as u32); // pr_pgrp
+        put_u32(&mut desc, self.pid as u32); // pr_sid
+        put_padded(&mut desc, &self.file_name, 16); // pr_fname
+        put_padded(&mut desc, &self.command, 80); // pr_psargs
+        desc
+    }
+
+    fn encode_file_map(&self) -> Vec<u8> {
+        let mut desc = Vec::new();
+        put_u64(&mut desc, self.mappings.len() as u64);
+        put_u64(&mut desc, PAGE_SIZE);
+        for mapping in &self.mappings {
+            put_u64(&mut desc, mapping.start);
+            put_u64(&mut desc, mapping.end);
+            put_u64(&mut desc, mapping.page_offset);
+        }
+        for mapping in &self.mappings {
+            desc.extend(&mapping.path);
+            desc.push(0);
+        }
+        desc
+    }
+}
+
+/// Encode a note with the given alignment.
+pub fn encode_note(name: &[u8], type_: u32, desc: &[u8], align: usize) -> Vec<u8> {
+    let name_size = if name.is_empty() { 0 } else { name.len() + 1 };
+    let mut note = Vec::new();
+    put_u32(&mut note, name_size as u32);
+    put_u32(&mut note, desc.len() as u32);
+    put_u32(&mut note, type_);
+    note.extend(name);
+    if name_size > 0 {
+        note.push(0);
+    }
+    note.resize(note.len().next_multiple_of(align), 0);
+    note.extend(desc);
+    note.resize(note.len().next_multiple_of(align), 0);
+    note
+}
+
+/// Encode the `NT_PRSTATUS` descriptor of the given thread.
+pub fn encode_prstatus(thread: &Thread) -> Vec<u8> {
+    let mut desc = Vec::with_capacity(336);
+    // pr_info: si_signo, si_code, si_errno.
+    put_u32(&mut desc, thread.cursig as u32);
+    put_u32(&mut desc, 0);
+    put_u32(&mut desc, 0);
+    put_u16(&mut desc, thread.cursig as u16); // pr_cursig
+    desc.extend([0, 0]);
+    put_u64(&mut desc, 0); // pr_sigpend
+    put_u64(&mut desc, 0); // pr_sighold
+    put_u32(&mut desc, thread.pid as u32); // pr_pid
+    put_u32(&mut desc, 1); // pr_ppid
+    put_u32(&mut desc, thread.pid as u32); // pr_pgrp
+    put_u32(&mut desc, thread.pid as u32); // pr_sid
+    desc.resize(desc.len() + 4 * 16, 0); // pr_utime, pr_stime, pr_cutime, pr_cstime
+    for reg in thread.gregs {
+        put_u64(&mut desc, reg);
+    }
+    put_u32(&mut desc, 1); // pr_fpvalid
+    desc.resize(336, 0);
+    desc
+}
+
+fn encode_phdr(segment: &Load, offset: u64) -> Vec<u8> {
+    let mut phdr = Vec::with_capacity(PHDR_SIZE);
+    put_u32(&mut phdr, segment.type_);
+    put_u32(&mut phdr, segment.flags);
+    put_u64(&mut phdr, offset);
+    put_u64(&mut phdr, segment.vaddr);
+    put_u64(&mut phdr, segment.paddr);
+    put_u64(&mut phdr, segment.data.len() as u64);
+    put_u64(&mut phdr, segment.memsz);
+    put_u64(&mut phdr, segment.align);
+    phdr
+}
+
+/// Encode section header 0, holding the program header count of a
+/// `PN_XNUM` core in `sh_info`.
+fn encode_xnum_shdr(phnum: u32) -> Vec<u8> {
+    let mut shdr = vec![0; 44];
+    put_u32(&mut shdr, phnum); // sh_info
+    shdr.resize(SHDR_SIZE, 0);
+    shdr
+}
+
+fn put_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend(value.to_le_bytes());
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend(value.to_le_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend(value.to_le_bytes());
+}
+
+fn put_padded(buf: &mut Vec<u8>, value: &[u8], len: usize) {
+    let start = buf.len();
+    buf.extend(&value[..value.len().min(len)]);
+    buf.resize(start + len, 0);
+}
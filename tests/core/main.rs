@@ -0,0 +1,7 @@
+//! Tests against synthetic cores assembled by [`builder::CoreBuilder`].
+
+mod builder;
+mod notes;
+mod parse;
+mod registers;
+mod segments;
@@ -0,0 +1,330 @@
+use std::ops::ControlFlow;
+
+use licore::note::NoteRegistry;
+use licore::{Core, ParseError, ParseOptions};
+
+use crate::builder::{
+    encode_note, encode_prstatus, reg, CoreBuilder, Thread, NT_AUXV, NT_FILE, NT_PRPSINFO,
+    NT_PRSTATUS,
+};
+
+#[test]
+fn linux_owned_standard_notes() {
+    let mut gregs = [0; 27];
+    gregs[reg::RIP] = 0x401000;
+    let data = CoreBuilder::new()
+        .owner(b"LINUX")
+        .pid(7)
+        .thread(7, gregs)
+        .mapping(0x400000, 0x401000, 0, b"/bin/true")
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    assert_eq!(core.process.pid, 7);
+    assert_eq!(core.threads.len(), 1);
+    assert_eq!(core.threads[0].pid, 7);
+    assert_eq!(core.threads[0].registers.rip, 0x401000);
+    assert_eq!(core.file_map.len(), 1);
+    assert!(core.extra_notes().is_empty());
+}
+
+#[test]
+fn note_overflowing_its_segment_is_rejected() {
+    let mut note = encode_note(b"CORE", 0x1234, &[0; 8], 4);
+    // Claim a descriptor larger than the rest of the segment.
+    note[4..8].copy_from_slice(&0x1000_u32.to_le_bytes());
+    let data = CoreBuilder::new()
+        .note_segment(note)
+        .load(0x400000, vec![0; 0x2000])
+        .build();
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(
+        error.to_string().contains("exceeds remaining segment data"),
+        "{error}"
+    );
+    assert!(
+        matches!(error, ParseError::NoteTooLarge { .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn notes_in_load_segment() {
+    let data = CoreBuilder::new()
+        .notes_as_load()
+        .pid(9)
+        .thread(9, [0; 27])
+        .mapping(0x400000, 0x401000, 0, b"/bin/true")
+        .build();
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("missing note"), "{error}");
+
+    let options = ParseOptions {
+        find_notes_in_load_segments: true,
+        ..Default::default()
+    };
+    let core = Core::parse_with_options(&data, options).unwrap();
+    assert_eq!(core.process.pid, 9);
+    assert_eq!(core.threads.len(), 1);
+    assert_eq!(core.file_map.len(), 1);
+
+    let mut visited = 0;
+    let _ = core.visit_notes(|_| {
+        visited += 1;
+        ControlFlow::Continue(())
+    });
+    assert_eq!(visited, 3);
+}
+
+#[test]
+fn notes_across_segments_keep_order_and_dedup() {
+    let first = [
+        encode_note(b"TEST", 1, b"one\0", 4),
+        encode_note(b"TEST", 2, b"two\0", 4),
+    ]
+    .concat();
+    let second = [
+        encode_note(b"TEST", 2, b"two\0", 4),
+        encode_note(b"TEST", 3, b"three\0", 4),
+    ]
+    .concat();
+    let data = CoreBuilder::new()
+        .note_segment(first)
+        .note_segment(second)
+        .build();
+    let extra_types =
+        |core: &Core| -> Vec<u32> { core.extra_notes().iter().map(|n| n.type_).collect() };
+
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(extra_types(&core), [1, 2, 2, 3]);
+
+    let mut visited = Vec::new();
+    let _ = core.visit_notes(|note| {
+        visited.push((note.name, note.type_));
+        ControlFlow::Continue(())
+    });
+    let expected = [
+        (&b"CORE"[..], NT_PRPSINFO),
+        (b"CORE", NT_FILE),
+        (b"TEST", 1),
+        (b"TEST", 2),
+        (b"TEST", 2),
+        (b"TEST", 3),
+    ];
+    assert_eq!(visited, expected);
+
+    let options = ParseOptions {
+        dedup_notes: true,
+        ..Default::default()
+    };
+    let core = Core::parse_with_options(&data, options).unwrap();
+    assert_eq!(extra_types(&core), [1, 2, 3]);
+
+    let mut visited = 0;
+    let _ = core.visit_notes(|_| {
+        visited += 1;
+        ControlFlow::Continue(())
+    });
+    assert_eq!(visited, 5);
+}
+
+#[test]
+fn visit_notes_stops_early() {
+    let data = CoreBuilder::new()
+        .note_segment(encode_note(b"TEST", 1, b"one\0", 4))
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let mut visited = Vec::new();
+    let result = core.visit_notes(|note| {
+        visited.push(note.type_);
+        if note.type_ == NT_FILE {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert!(result.is_break());
+    assert_eq!(visited, [NT_PRPSINFO, NT_FILE]);
+}
+
+#[test]
+fn note_without_owner_name() {
+    let notes = [
+        encode_note(b"", 0x77, b"anonymous", 4),
+        encode_note(b"TEST", 0x78, b"named", 4),
+    ]
+    .concat();
+    // The note header is followed directly by the descriptor.
+    assert_eq!(&notes[..4], &0_u32.to_le_bytes());
+    assert_eq!(&notes[12..21], b"anonymous");
+
+    let data = CoreBuilder::new().note_segment(notes).build();
+    let core = Core::parse(&data).unwrap();
+
+    let extra = core.extra_notes();
+    assert_eq!(extra.len(), 2);
+    assert_eq!((extra[0].name, extra[0].type_), (&b""[..], 0x77));
+    assert_eq!(extra[0].desc, b"anonymous");
+    assert_eq!((extra[1].name, extra[1].type_), (&b"TEST"[..], 0x78));
+    assert_eq!(extra[1].desc, b"named");
+    assert_eq!(core.notes_by(b"", 0x77).collect::<Vec<_>>(), [b"anonymous"]);
+    assert!(core.warnings().is_empty());
+}
+
+#[test]
+fn raw_prstatus_with_duplicate_pids() {
+    let mut gregs = [0; 27];
+    gregs[reg::RIP] = 0x1000;
+    let first = Thread {
+        pid: 7,
+        gregs,
+        cursig: 0,
+    };
+    gregs[reg::RIP] = 0x2000;
+    let second = Thread {
+        pid: 7,
+        gregs,
+        cursig: 0,
+    };
+    let data = CoreBuilder::new()
+        .thread_with(first.clone())
+        .thread_with(second.clone())
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    assert_eq!(core.threads[0].raw_prstatus(), encode_prstatus(&first));
+    assert_eq!(core.threads[1].raw_prstatus(), encode_prstatus(&second));
+
+    let owned = core.into_owned();
+    assert_eq!(owned.threads[1].raw_prstatus(), encode_prstatus(&second));
+}
+
+#[test]
+fn linux_owned_auxv() {
+    const STACK: u64 = 0x7fff_0000;
+    const AT_EXECFN: u64 = 31;
+
+    let mut stack = Vec::new();
+    for word in [1, STACK + 0x100, 0, STACK + 0x110, 0] {
+        stack.extend(u64::to_le_bytes(word));
+    }
+    let auxv: Vec<u8> = [AT_EXECFN, STACK + 0x120, 0, 0]
+        .iter()
+        .flat_map(|w| w.to_le_bytes())
+        .collect();
+    stack.extend(&auxv);
+    stack.resize(0x100, 0);
+    stack.extend(b"./app\0");
+    stack.resize(0x110, 0);
+    stack.extend(b"HOME=/\0");
+    stack.resize(0x120, 0);
+    stack.extend(b"/bin/app\0");
+    stack.resize(0x1000, 0);
+
+    let data = CoreBuilder::new()
+        .owner(b"LINUX")
+        .note_segment(encode_note(b"LINUX", NT_AUXV, &auxv, 4))
+        .load(STACK, stack)
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let (argv, envp) = core.recover_argv_envp().unwrap();
+    assert_eq!(argv, [b"./app"]);
+    assert_eq!(envp, [b"HOME=/"]);
+    assert!(core.extra_notes().is_empty());
+}
+
+#[test]
+fn per_thread_ioperm_and_debug_registers() {
+    const NT_386_IOPERM: u32 = 0x201;
+    const NT_DEBUGREG: u32 = 0x4000;
+
+    let thread = |pid| Thread {
+        pid,
+        gregs: [0; 27],
+        cursig: 0,
+    };
+    let debugregs: [u64; 8] = std::array::from_fn(|i| 0x1000 + i as u64);
+    let desc: Vec<u8> = debugregs.iter().flat_map(|r| r.to_le_bytes()).collect();
+    let notes = [
+        encode_note(b"CORE", NT_PRSTATUS, &encode_prstatus(&thread(1)), 4),
+        encode_note(b"LINUX", NT_386_IOPERM, &[0xff; 16], 4),
+        encode_note(b"CORE", NT_DEBUGREG, &desc, 4),
+        encode_note(b"CORE", NT_PRSTATUS, &encode_prstatus(&thread(2)), 4),
+    ]
+    .concat();
+    let data = CoreBuilder::new().note_segment(notes).build();
+
+    let options = ParseOptions {
+        debug_registers_note_type: Some(NT_DEBUGREG),
+        ..Default::default()
+    };
+    let core = Core::parse_with_options(&data, options).unwrap();
+    assert_eq!(
+        core.threads[0].io_permission_bitmap(),
+        Some(&[0xff; 16][..])
+    );
+    assert_eq!(core.threads[0].debug_registers(), Some(debugregs));
+    assert_eq!(core.threads[1].io_permission_bitmap(), None);
+    assert_eq!(core.threads[1].debug_registers(), None);
+    assert!(core.extra_notes().is_empty());
+
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.threads[0].debug_registers(), None);
+    assert_eq!(core.extra_notes().len(), 1);
+}
+
+#[test]
+fn short_debug_register_note_is_rejected() {
+    let thread = Thread {
+        pid: 1,
+        gregs: [0; 27],
+        cursig: 0,
+    };
+    let notes = [
+        encode_note(b"CORE", NT_PRSTATUS, &encode_prstatus(&thread), 4),
+        encode_note(b"CORE", 0x4000, &[0; 16], 4),
+    ]
+    .concat();
+    let data = CoreBuilder::new().note_segment(notes).build();
+
+    let options = ParseOptions {
+        debug_registers_note_type: Some(0x4000),
+        ..Default::default()
+    };
+    let error = Core::parse_with_options(&data, options).unwrap_err();
+    assert!(error.to_string().contains("debug register"), "{error}");
+}
+
+#[test]
+fn custom_notes_from_registry() {
+    let notes = [
+        encode_note(b"ACME", 1, &7_u32.to_le_bytes(), 4),
+        encode_note(b"ACME", 2, &[0; 4], 4),
+        encode_note(b"OTHER", 1, &[0; 4], 4),
+    ]
+    .concat();
+    let data = CoreBuilder::new()
+        .thread(1, [0; 27])
+        .note_segment(notes)
+        .build();
+
+    let mut registry = NoteRegistry::new();
+    registry.register(b"ACME", 1, |desc| {
+        u32::from_le_bytes(desc.try_into().unwrap())
+    });
+    // Built-in notes are never passed to registered decoders.
+    registry.register(b"CORE", NT_PRSTATUS, |_| -> u32 { unreachable!() });
+
+    let core = Core::parse_with_registry(&data, &registry).unwrap();
+    assert_eq!(core.custom_notes.len(), 1);
+    assert_eq!(core.custom_notes[0].note.name, b"ACME");
+    assert_eq!(core.custom_notes[0].value::<u32>(), Some(&7));
+    assert_eq!(core.custom_notes[0].value::<u64>(), None);
+
+    let core = Core::parse(&data).unwrap();
+    assert!(core.custom_notes.is_empty());
+}
@@ -0,0 +1,343 @@
+use std::borrow::Cow;
+#[cfg(feature = "lz4")]
+use std::io::Write;
+
+use licore::{
+    Core, CoreBuffer, ParseError, ParseOptions, ParseWarning, ProcessInfo, Registers, Segment,
+    SegmentFlags, SegmentId, ThreadId, ThreadInfo,
+};
+
+use crate::builder::{reg, CoreBuilder, Thread, E_PHNUM_OFFSET, PAGE_SIZE, PN_XNUM};
+
+#[test]
+fn builder_core_round_trip() {
+    let mut gregs = [0; 27];
+    gregs[reg::RIP] = 0x401000;
+    gregs[reg::RSP] = 0x7fff_0100;
+    let data = CoreBuilder::new()
+        .pid(42)
+        .command(b"./app --flag ")
+        .thread(42, gregs)
+        .thread(43, [0; 27])
+        .mapping(0x400000, 0x402000, 0, b"/usr/bin/app")
+        .load(0x400000, vec![0xcc; 0x2000])
+        .load(0x7fff_0000, vec![0xab; 0x1000])
+        .build();
+
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.process.pid, 42);
+    assert_eq!(core.process.args(), [&b"./app"[..], b"--flag"]);
+
+    let pids: Vec<_> = core.threads.iter().map(|t| t.pid).collect();
+    assert_eq!(pids, [42, 43]);
+    assert_eq!(core.threads[0].registers.rip, 0x401000);
+    assert_eq!(core.threads[0].registers.rsp, 0x7fff_0100);
+
+    assert_eq!(core.file_map.len(), 1);
+    assert_eq!(core.file_map[0].file_path, b"/usr/bin/app");
+    assert_eq!(
+        core.file_map[0].vm_start..core.file_map[0].vm_end,
+        0x400000..0x402000
+    );
+
+    assert_eq!(core.segments.len(), 2);
+    assert_eq!(core.segments[0].flags, SegmentFlags::from_bits(6));
+    assert_eq!(core.read_memory(0x401ffe, 2), Some(&[0xcc, 0xcc][..]));
+    assert_eq!(core.read_memory(0x7fff_0100, 1), Some(&[0xab][..]));
+    assert!(core.warnings().is_empty());
+}
+
+#[test]
+fn program_header_count_exceeding_file_is_rejected() {
+    let mut data = CoreBuilder::new().build();
+    data.truncate(0x100);
+    data[E_PHNUM_OFFSET..][..2].copy_from_slice(&0xfffe_u16.to_le_bytes());
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("Elf64_Phdr"), "{error}");
+    assert!(
+        matches!(
+            error,
+            ParseError::TruncatedHeader {
+                name: "Elf64_Phdr",
+                ..
+            }
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn segment_beyond_end_of_file_is_rejected() {
+    let mut data = CoreBuilder::new().load(0x400000, vec![0; 0x2000]).build();
+    data.truncate(data.len() - 0x1000);
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("invalid file range"), "{error}");
+    assert!(
+        matches!(error, ParseError::SegmentOutOfBounds { size: 0x2000, .. }),
+        "{error:?}"
+    );
+
+    let options = ParseOptions {
+        tolerate_truncation: true,
+        ..Default::default()
+    };
+    let core = Core::parse_with_options(&data, options).unwrap();
+    assert_eq!(core.segments[0].data.len(), 0x1000);
+}
+
+#[test]
+fn pn_xnum_without_section_headers_is_rejected() {
+    let mut data = CoreBuilder::new().build();
+    data.truncate(0x100);
+    data[E_PHNUM_OFFSET..][..2].copy_from_slice(&0xffff_u16.to_le_bytes());
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("PN_XNUM"), "{error}");
+}
+
+#[test]
+fn file_map_count_exceeding_note_is_rejected() {
+    let mut data = CoreBuilder::new().build();
+    // Without mappings, the NT_FILE descriptor is just the count and the
+    // page size.
+    let header = [0_u64.to_le_bytes(), PAGE_SIZE.to_le_bytes()].concat();
+    let pos = data.windows(16).position(|w| w == header).unwrap();
+    data[pos..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("NT_FILE"), "{error}");
+}
+
+#[test]
+fn pn_xnum_program_header_count() {
+    let data = CoreBuilder::new()
+        .xnum()
+        .load(0x400000, vec![0; 0x1000])
+        .load(0x600000, vec![0; 0x1000])
+        .build();
+    assert_eq!(data[E_PHNUM_OFFSET..][..2], PN_XNUM.to_le_bytes());
+
+    let core = Core::parse(&data).unwrap();
+    let starts: Vec<_> = core.segments.iter().map(|s| s.vm_start).collect();
+    assert_eq!(starts, [0x400000, 0x600000]);
+}
+
+#[test]
+fn linux_os_abi() {
+    let data = CoreBuilder::new().osabi(3).pid(11).build();
+    let core = Core::parse(&data).unwrap();
+
+    assert_eq!(core.os_abi(), 3);
+    assert_eq!(core.abi_version(), 0);
+    assert_eq!(core.process.pid, 11);
+}
+
+#[test]
+fn unknown_os_abi_is_rejected() {
+    let data = CoreBuilder::new().osabi(9).build();
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("osabi"), "{error}");
+}
+
+#[test]
+fn out_of_bounds_section_headers_are_ignored() {
+    let mut data = CoreBuilder::new().pid(5).build();
+    // e_shoff, e_shentsize and e_shnum.
+    data[40..][..8].copy_from_slice(&0x10_0000_u64.to_le_bytes());
+    data[58..][..2].copy_from_slice(&64_u16.to_le_bytes());
+    data[60..][..2].copy_from_slice(&1_u16.to_le_bytes());
+
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.process.pid, 5);
+    assert!(matches!(
+        core.warnings(),
+        [ParseWarning::InvalidSectionHeaders { .. }]
+    ));
+}
+
+#[test]
+fn invalid_note_section_is_ignored() {
+    let mut data = CoreBuilder::new().xnum().pid(5).build();
+    // Turn section header 0 into an SHT_NOTE section pointing past the end
+    // of the file.
+    let shdr = data.len() - 64;
+    data[shdr + 4..][..4].copy_from_slice(&7_u32.to_le_bytes());
+    data[shdr + 24..][..8].copy_from_slice(&0x10_0000_u64.to_le_bytes());
+    data[shdr + 32..][..8].copy_from_slice(&0x100_u64.to_le_bytes());
+
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.process.pid, 5);
+    assert!(matches!(
+        core.warnings(),
+        [ParseWarning::InvalidNoteSection {
+            offset: 0x10_0000,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn process_current_signal_from_first_signaled_thread() {
+    let thread = |pid, cursig| Thread {
+        pid,
+        gregs: [0; 27],
+        cursig,
+    };
+    let data = CoreBuilder::new()
+        .thread_with(thread(1, 0))
+        .thread_with(thread(2, 11))
+        .thread_with(thread(3, 6))
+        .build();
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.process.current_signal(), Some(11));
+
+    let data = CoreBuilder::new().thread(1, [0; 27]).build();
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.process.current_signal(), None);
+}
+
+#[test]
+fn core_buffer_over_owned_data() {
+    let data = CoreBuilder::new()
+        .pid(9)
+        .load(0x400000, vec![0xaa; 0x1000])
+        .load(0x600000, vec![0xbb; 0x1000])
+        .build();
+    let buffer = CoreBuffer::new(data.clone(), ParseOptions::default()).unwrap();
+
+    assert_eq!(buffer.core().process.pid, 9);
+    assert_eq!(buffer.core().segments.len(), 2);
+    assert_eq!(buffer.segment_data(1), Some(&[0xbb; 0x1000][..]));
+    assert_eq!(buffer.segment_data(2), None);
+    assert_eq!(buffer.read_memory(0x400ffe, 2), Some(&[0xaa, 0xaa][..]));
+    assert_eq!(buffer.read_memory(0x400fff, 2), None);
+    assert_eq!(buffer.into_data(), data);
+
+    let error = CoreBuffer::new(&data[..0x40], ParseOptions::default()).unwrap_err();
+    assert!(!error.to_string().is_empty());
+}
+
+#[test]
+fn uncompressed_core_is_borrowed() {
+    let data = CoreBuilder::new().build();
+    assert!(matches!(licore::decompress(&data), Ok(Cow::Borrowed(_))));
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn lz4_decompression_size_limit() {
+    use lz4_flex::frame::{FrameEncoder, FrameInfo};
+
+    let data = CoreBuilder::new().load(0x400000, vec![0; 0x10000]).build();
+    let limited = ParseOptions {
+        max_decompressed_size: data.len() as u64 - 1,
+        ..Default::default()
+    };
+
+    // Frames may or may not record the decompressed size in their header.
+    let size = Some(data.len() as u64);
+    for frame_info in [FrameInfo::new(), FrameInfo::new().content_size(size)] {
+        let mut encoder = FrameEncoder::with_frame_info(frame_info, Vec::new());
+        encoder.write_all(&data).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(licore::decompress(&compressed).unwrap(), &data[..]);
+        let error = licore::decompress_with_options(&compressed, limited).unwrap_err();
+        assert!(error.to_string().contains("maximum size"), "{error}");
+    }
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn xz_decompression_size_limit() {
+    let data = CoreBuilder::new().load(0x400000, vec![0; 0x10000]).build();
+    let limited = ParseOptions {
+        max_decompressed_size: data.len() as u64 - 1,
+        ..Default::default()
+    };
+
+    let mut compressed = Vec::new();
+    lzma_rs::xz_compress(&mut &data[..], &mut compressed).unwrap();
+
+    assert_eq!(licore::decompress(&compressed).unwrap(), &data[..]);
+    let error = licore::decompress_with_options(&compressed, limited).unwrap_err();
+    assert!(error.to_string().contains("maximum size"), "{error}");
+}
+
+#[test]
+fn diff_matches_threads_modules_and_ranges() {
+    let mut gregs = [0; 27];
+    let old = CoreBuilder::new()
+        .thread(1, gregs)
+        .thread(2, gregs)
+        .mapping(0x400000, 0x401000, 0, b"/bin/app")
+        .mapping(0x500000, 0x501000, 0, b"/lib/old.so")
+        .load(0x400000, vec![0; 0x1000])
+        .load(0x500000, vec![0; 0x1000])
+        .build();
+    gregs[reg::RIP] = 0x401000;
+    let new = CoreBuilder::new()
+        .thread(1, gregs)
+        .thread(3, [0; 27])
+        .mapping(0x400000, 0x401000, 0, b"/bin/app")
+        .mapping(0x600000, 0x601000, 0, b"/lib/new.so")
+        .load(0x400000, vec![0; 0x1000])
+        .load(0x600000, vec![0; 0x1000])
+        .build();
+    let old = Core::parse(&old).unwrap();
+    let new = Core::parse(&new).unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.thread_changes.len(), 1);
+    assert_eq!(diff.thread_changes[0].id, ThreadId(1));
+    assert_eq!(diff.added_threads, [ThreadId(3)]);
+    assert_eq!(diff.removed_threads, [ThreadId(2)]);
+    assert_eq!(diff.added_modules, [b"/lib/new.so"]);
+    assert_eq!(diff.removed_modules, [b"/lib/old.so"]);
+    assert_eq!(diff.added_ranges, vec![0x600000..0x601000]);
+    assert_eq!(diff.removed_ranges, vec![0x500000..0x501000]);
+    assert!(old.diff(&old).is_empty());
+}
+
+#[test]
+fn core_built_from_parts() {
+    let stack = [0xaa; 0x100];
+    let segment = Segment {
+        id: SegmentId(0),
+        vm_start: 0x7fff_0000,
+        vm_end: 0x7fff_0100,
+        physical_address: 0,
+        flags: SegmentFlags::READ,
+        align: 1,
+        data: &stack,
+    };
+
+    let mut process = ProcessInfo::default().with_current_signal(Some(11));
+    process.pid = 42;
+
+    let prstatus = [0; 336];
+    let mut thread = ThreadInfo::default()
+        .with_raw_prstatus(&prstatus)
+        .with_debug_registers(Some([1; 8]));
+    thread.pid = 42;
+    thread.current_signal = Some(11);
+    thread.registers = Registers {
+        rsp: 0x7fff_0080,
+        ..Registers::default()
+    };
+
+    let core = Core::new(vec![segment], process, vec![thread], Vec::new(), None);
+    assert_eq!(core.process.current_signal(), Some(11));
+    assert_eq!(core.main_thread().map(|t| t.pid), Some(42));
+    assert_eq!(core.threads[0].raw_prstatus().len(), 336);
+    assert_eq!(core.threads[0].debug_registers(), Some([1; 8]));
+    assert_eq!(core.threads[0].io_permission_bitmap(), None);
+    assert_eq!(core.read_memory(0x7fff_0080, 4), Some(&[0xaa; 4][..]));
+    assert_eq!(core.permissions_at(0x7fff_0000), Some(SegmentFlags::READ));
+    assert_eq!(core.bytes_at_file_offset(0, 1), None);
+    assert_eq!(core.os_abi(), 0);
+    assert!(core.warnings().is_empty());
+}
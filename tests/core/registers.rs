@@ -0,0 +1,18 @@
+use licore::Core;
+
+use crate::builder::{reg, CoreBuilder};
+
+#[test]
+fn user_regs_round_trip() {
+    let gregs: [u64; 27] = std::array::from_fn(|i| 0x1111_0000_0000 + i as u64);
+    let data = CoreBuilder::new().thread(1, gregs).build();
+    let core = Core::parse(&data).unwrap();
+    let registers = &core.threads[0].registers;
+
+    assert_eq!(registers.rip, gregs[reg::RIP]);
+    assert_eq!(registers.rsp, gregs[reg::RSP]);
+    assert_eq!(core.threads[0].raw_gregset(), gregs);
+
+    let expected: Vec<u8> = gregs.iter().flat_map(|r| r.to_le_bytes()).collect();
+    assert_eq!(registers.to_user_regs_bytes()[..], expected[..]);
+}
@@ -0,0 +1,429 @@
+use std::cmp::Ordering;
+use std::io;
+
+use licore::{Core, ParseOptions, ParseWarning, RegionKind, MAX_RECONSTRUCTED_FILE_SIZE};
+
+use crate::builder::{encode_note, reg, CoreBuilder, Load, NT_AUXV};
+
+#[test]
+fn segment_and_mapping_boundaries() {
+    let data = CoreBuilder::new()
+        .mapping(0x400000, 0x401000, 0, b"/usr/bin/app")
+        .load(0x400000, vec![0; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let segment = &core.segments[0];
+    assert_eq!(segment.len(), 0x1000);
+    assert_eq!(segment.range(), 0x400000..0x401000);
+    assert!(!segment.contains(0x3fffff));
+    assert!(segment.contains(0x400000));
+    assert!(segment.contains(0x400fff));
+    assert!(!segment.contains(0x401000));
+
+    let mapping = &core.file_map[0];
+    assert!(!mapping.contains(0x3fffff));
+    assert!(mapping.contains(0x400000));
+    assert!(mapping.contains(0x400fff));
+    assert!(!mapping.contains(0x401000));
+}
+
+#[test]
+fn zero_alignment_means_unaligned() {
+    let data = CoreBuilder::new()
+        .load_with(Load {
+            align: 0,
+            ..Load::new(0x400123, vec![1; 0x10])
+        })
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    assert_eq!(core.segments[0].align, 0);
+    assert_eq!(core.read_memory(0x400123, 1), Some(&[1][..]));
+}
+
+#[test]
+fn misaligned_segment_is_rejected() {
+    let data = CoreBuilder::new()
+        .load_with(Load {
+            align: 0x1000,
+            ..Load::new(0x400123, vec![1; 0x10])
+        })
+        .build();
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("unaligned p_vaddr"), "{error}");
+}
+
+#[test]
+fn notes_only_core() {
+    let mut gregs = [0; 27];
+    gregs[reg::RSP] = 0x7fff_0000;
+    let data = CoreBuilder::new().pid(5).thread(5, gregs).build();
+    let core = Core::parse(&data).unwrap();
+
+    assert!(core.segments.is_empty());
+    assert_eq!(core.process.pid, 5);
+    assert_eq!(core.threads[0].registers.rsp, 0x7fff_0000);
+
+    assert_eq!(core.segment_at(0x7fff_0000), None);
+    assert_eq!(core.read_memory(0x7fff_0000, 8), None);
+    assert_eq!(core.read_memory_spanning(0x7fff_0000, 8), None);
+    assert_eq!(core.read_c_string_at(0x7fff_0000, 8), None);
+    assert!(core.segments_overlapping(0..usize::MAX).is_empty());
+    assert_eq!(core.thread_stacks(), [(5, None)]);
+}
+
+#[test]
+fn segment_reader_copies_data() {
+    let bytes: Vec<u8> = (0..=255).cycle().take(0x1800).collect();
+    let data = CoreBuilder::new().load(0x400000, bytes.clone()).build();
+    let core = Core::parse(&data).unwrap();
+
+    let mut copy = Vec::new();
+    let copied = io::copy(&mut core.segments[0].reader(), &mut copy).unwrap();
+    assert_eq!(copied, 0x1800);
+    assert_eq!(copy, bytes);
+}
+
+#[test]
+fn overflowing_segment_range_is_rejected() {
+    let data = CoreBuilder::new()
+        .load_with(Load {
+            align: 0,
+            ..Load::new(u64::MAX, vec![0; 0x10])
+        })
+        .build();
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(error.to_string().contains("overflows"), "{error}");
+}
+
+#[test]
+fn inverted_mapping_range_is_rejected() {
+    let data = CoreBuilder::new()
+        .mapping(0x401000, 0x400000, 0, b"/bin/true")
+        .build();
+
+    let error = Core::parse(&data).unwrap_err();
+    assert!(
+        error.to_string().contains("ends before it starts"),
+        "{error}"
+    );
+}
+
+#[test]
+fn address_space_map_presence() {
+    let data = CoreBuilder::new()
+        .mapping(0x400000, 0x402000, 0, b"/usr/bin/app")
+        .mapping(0x600000, 0x601000, 0, b"/usr/lib/libc.so")
+        .load(0x400000, vec![0; 0x2000])
+        .load_with(Load {
+            memsz: 0x2000,
+            ..Load::new(0x600000, vec![0; 0x800])
+        })
+        .build();
+    let options = ParseOptions {
+        allow_size_mismatch: true,
+        ..Default::default()
+    };
+    let core = Core::parse_with_options(&data, options).unwrap();
+
+    let regions: Vec<_> = core
+        .address_space_map()
+        .into_iter()
+        .map(|r| (r.start, r.end, r.kind, r.file_path, r.present))
+        .collect();
+    let expected = [
+        (
+            0x400000,
+            0x402000,
+            RegionKind::Mapped,
+            Some(&b"/usr/bin/app"[..]),
+            true,
+        ),
+        (0x402000, 0x600000, RegionKind::Gap, None, false),
+        (
+            0x600000,
+            0x601000,
+            RegionKind::Mapped,
+            Some(b"/usr/lib/libc.so"),
+            false,
+        ),
+        (0x601000, 0x602000, RegionKind::Mapped, None, false),
+    ];
+    assert_eq!(regions, expected);
+}
+
+#[test]
+fn reconstruct_file_from_mappings() {
+    let data = CoreBuilder::new()
+        .mapping(0x400000, 0x401000, 0, b"/lib/a.so")
+        .mapping(0x401000, 0x403000, 1, b"/lib/a.so")
+        .load(0x400000, vec![1; 0x1000])
+        // Only the first page of the second mapping is present.
+        .load(0x401000, vec![2; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let file = core.reconstruct_file(b"/lib/a.so");
+    assert_eq!(file.len(), 0x2000);
+    assert!(file[..0x1000].iter().all(|&b| b == 1));
+    assert!(file[0x1000..].iter().all(|&b| b == 2));
+
+    assert!(core.reconstruct_file(b"/lib/b.so").is_empty());
+}
+
+#[test]
+fn reconstruct_file_zero_fills_gaps() {
+    let data = CoreBuilder::new()
+        .mapping(0x400000, 0x401000, 0, b"/lib/a.so")
+        .mapping(0x402000, 0x403000, 4, b"/lib/a.so")
+        .load(0x400000, vec![1; 0x1000])
+        .load(0x402000, vec![2; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let file = core.reconstruct_file(b"/lib/a.so");
+    assert_eq!(file.len(), 0x5000);
+    assert!(file[..0x1000].iter().all(|&b| b == 1));
+    assert!(file[0x1000..0x4000].iter().all(|&b| b == 0));
+    assert!(file[0x4000..].iter().all(|&b| b == 2));
+}
+
+#[test]
+fn reconstruct_file_skips_data_beyond_size_limit() {
+    let data = CoreBuilder::new()
+        .mapping(0x400000, 0x401000, 0, b"/lib/a.so")
+        .mapping(
+            0x401000,
+            0x402000,
+            MAX_RECONSTRUCTED_FILE_SIZE as u64 / 0x1000,
+            b"/lib/a.so",
+        )
+        .load(0x400000, vec![1; 0x2000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    assert_eq!(core.reconstruct_file(b"/lib/a.so"), vec![1; 0x1000]);
+}
+
+#[test]
+fn divergent_physical_address_is_reported() {
+    let data = CoreBuilder::new()
+        .load(0x400000, vec![0; 0x1000])
+        .load_with(Load {
+            paddr: 0x500000,
+            ..Load::new(0x500000, vec![0; 0x1000])
+        })
+        .load_with(Load {
+            paddr: 0x1000,
+            ..Load::new(0x600000, vec![0; 0x1000])
+        })
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    assert_eq!(core.segments[2].physical_address, 0x1000);
+    let [ParseWarning::DivergentPhysicalAddress {
+        segment,
+        virtual_address: 0x600000,
+        physical_address: 0x1000,
+    }] = core.warnings()
+    else {
+        panic!("unexpected warnings: {:?}", core.warnings());
+    };
+    assert_eq!(*segment, core.segments[2].id);
+}
+
+#[test]
+fn segments_overlapping_touching_ranges() {
+    let data = CoreBuilder::new()
+        .load(0x400000, vec![0; 0x1000])
+        .load(0x401000, vec![0; 0x1000])
+        .load(0x500000, vec![0; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+    let starts = |range| -> Vec<_> {
+        core.segments_overlapping(range)
+            .iter()
+            .map(|s| s.vm_start)
+            .collect()
+    };
+
+    assert_eq!(starts(0x3ff000..0x400000), []);
+    assert_eq!(starts(0x3ff000..0x400001), [0x400000]);
+    assert_eq!(starts(0x400fff..0x401000), [0x400000]);
+    assert_eq!(starts(0x400fff..0x401001), [0x400000, 0x401000]);
+    assert_eq!(starts(0x401000..0x401001), [0x401000]);
+    assert_eq!(starts(0x402000..0x500000), []);
+    assert_eq!(starts(0x401fff..0x500001), [0x401000, 0x500000]);
+    assert_eq!(starts(0x501000..usize::MAX), []);
+    assert_eq!(starts(0x400800..0x400800), []);
+}
+
+#[test]
+fn lookups_with_overlapping_segments() {
+    let data = CoreBuilder::new()
+        .load(0x400000, vec![1; 0x3000])
+        .load(0x401000, vec![2; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+    assert!(matches!(
+        core.warnings(),
+        [ParseWarning::OverlappingSegments { .. }]
+    ));
+
+    let starts: Vec<_> = core
+        .segments_overlapping(0x402000..0x402800)
+        .iter()
+        .map(|s| s.vm_start)
+        .collect();
+    assert_eq!(starts, [0x400000]);
+
+    let starts: Vec<_> = core
+        .segments_overlapping(0x401800..0x402800)
+        .iter()
+        .map(|s| s.vm_start)
+        .collect();
+    assert_eq!(starts, [0x400000, 0x401000]);
+
+    assert_eq!(core.segment_at(0x401800).unwrap().vm_start, 0x401000);
+    assert_eq!(core.segment_at(0x402000).unwrap().vm_start, 0x400000);
+    assert_eq!(core.read_memory(0x402000, 1), Some(&[1][..]));
+}
+
+#[test]
+fn segments_compare_by_address_range_only() {
+    let data = CoreBuilder::new()
+        .load(0x500000, vec![1; 0x1000])
+        .load(0x400000, vec![2; 0x1000])
+        .load(0x400000, vec![3; 0x1000])
+        .build();
+    let mut core = Core::parse(&data).unwrap();
+
+    let segments = &core.segments;
+    assert_eq!(segments[1], segments[2]);
+    assert_eq!(segments[1].cmp(&segments[2]), Ordering::Equal);
+    assert!(segments[1] < segments[0]);
+
+    core.sort_segments_by_address();
+    let starts: Vec<_> = core.segments.iter().map(|s| s.vm_start).collect();
+    assert_eq!(starts, [0x400000, 0x400000, 0x500000]);
+}
+
+#[test]
+fn format_maps_line_permissions() {
+    let data = CoreBuilder::new()
+        .mapping(0x400000, 0x401000, 2, b"/bin/app")
+        // Not covered by a segment.
+        .mapping(0x500000, 0x501000, 0, b"/bin/app")
+        .load(0x400000, vec![0; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let line = core.file_map[0].format_maps_line();
+    assert_eq!(
+        line,
+        format!("{:<72} /bin/app", "00400000-00401000 rw-p 00002000 00:00 0")
+    );
+
+    let line = core.file_map[1].format_maps_line();
+    assert!(
+        line.starts_with("00500000-00501000 ---- 00000000 "),
+        "{line}"
+    );
+}
+
+#[test]
+fn link_map_with_corrupt_program_header_count() {
+    const BASE: u64 = 0x400000;
+    const PHDRS: u64 = 0xf90;
+    const AT_PHDR: u64 = 3;
+    const AT_PHENT: u64 = 4;
+    const AT_PHNUM: u64 = 5;
+
+    fn put(memory: &mut [u8], offset: u64, words: &[u64]) {
+        let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        memory[offset as usize..][..bytes.len()].copy_from_slice(&bytes);
+    }
+
+    // The program header table, PT_PHDR and PT_DYNAMIC, ends the segment.
+    let mut memory = vec![0; 0x1000];
+    put(&mut memory, PHDRS, &[6, 0, BASE + PHDRS]);
+    put(&mut memory, PHDRS + 56, &[2, 0, BASE + 0x100]);
+    // The dynamic section points DT_DEBUG to r_debug, whose r_map points to
+    // a single link_map entry.
+    put(&mut memory, 0x100, &[21, BASE + 0x200, 0, 0]);
+    put(&mut memory, 0x200, &[1, BASE + 0x300]);
+    put(&mut memory, 0x300, &[0, BASE + 0x400, BASE + 0x100, 0]);
+    memory[0x400..][..9].copy_from_slice(b"/bin/app\0");
+
+    let build = |phent: u64, phnum: u64| {
+        let auxv = [
+            AT_PHDR,
+            BASE + PHDRS,
+            AT_PHENT,
+            phent,
+            AT_PHNUM,
+            phnum,
+            0,
+            0,
+        ];
+        let auxv: Vec<u8> = auxv.iter().flat_map(|w| w.to_le_bytes()).collect();
+        CoreBuilder::new()
+            .note_segment(encode_note(b"CORE", NT_AUXV, &auxv, 4))
+            .load(BASE, memory.clone())
+            .build()
+    };
+
+    // Reading stops at the end of the segment, after the real headers.
+    let data = build(56, u64::MAX);
+    let core = Core::parse(&data).unwrap();
+    let entries = core.walk_link_map().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, b"/bin/app");
+    assert_eq!(entries[0].dynamic, BASE as usize + 0x100);
+
+    // Only 64-bit program headers are supported.
+    let data = build(32, 2);
+    let core = Core::parse(&data).unwrap();
+    assert_eq!(core.walk_link_map(), None);
+}
+
+#[test]
+fn read_memory_spanning_adjacent_segments() {
+    let data = CoreBuilder::new()
+        .load(0x400000, vec![1; 0x1000])
+        .load(0x401000, vec![2; 0x1000])
+        .build();
+    let core = Core::parse(&data).unwrap();
+
+    let bytes = core.read_memory_spanning(0x400ffe, 4).unwrap();
+    assert_eq!(bytes, [1, 1, 2, 2]);
+    assert_eq!(core.read_memory_spanning(0x401ffe, 4), None);
+    // A huge length must not be allocated up front.
+    assert_eq!(core.read_memory_spanning(0x400000, 1 << 60), None);
+}
+
+#[test]
+fn lookups_after_modifying_segments() {
+    let data = CoreBuilder::new()
+        .load(0x400000, vec![1; 0x1000])
+        .load(0x600000, vec![2; 0x1000])
+        .build();
+    let mut core = Core::parse(&data).unwrap();
+
+    // The stale index must not return a segment that doesn't contain the
+    // address.
+    let removed = core.segments.remove(0);
+    assert_eq!(core.segment_at(0x400000), None);
+    assert_eq!(core.read_memory(0x600000, 1), None);
+
+    core.rebuild_segment_index();
+    assert_eq!(core.read_memory(0x600000, 1), Some(&[2][..]));
+
+    core.segments.push(removed);
+    core.rebuild_segment_index();
+    assert_eq!(core.read_memory(0x400000, 1), Some(&[1][..]));
+    assert_eq!(core.segments_overlapping(0..usize::MAX).len(), 2);
+}